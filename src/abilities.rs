@@ -0,0 +1,198 @@
+use crate::{
+    projectiles::Projectile,
+    statuses::{add_status, DamageBoostStatus, SpeedBoostStatus, Status},
+    utils::Position,
+};
+
+/// Times an ability can be improved by Purity Seals.
+pub const MAX_ABILITY_RANK: u32 = 3;
+// Every rank shortens the cooldown and makes the ability hit harder or last longer
+const RANK_COOLDOWN_FACTOR: f32 = 0.85;
+const RANK_POTENCY_BONUS: f32 = 0.25;
+
+// Frag grenade
+const GRENADE_MAX_RANGE: f32 = 500.0;
+const GRENADE_DAMAGE: f32 = 40.0;
+const GRENADE_RADIUS: f32 = 130.0;
+
+// Orbital lance
+const LANCE_MAX_RANGE: f32 = 700.0;
+const LANCE_DELAY: f32 = 1.2;
+const LANCE_DAMAGE: f32 = 120.0;
+const LANCE_RADIUS: f32 = 110.0;
+
+// Litany of hate
+const LITANY_DURATION: f32 = 5.0;
+const LITANY_SPEED_MULTIPLIER: f32 = 1.3;
+const LITANY_DAMAGE_MULTIPLIER: f32 = 1.5;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AbilityKind {
+    /// Thrown at the crosshair, exploding where it lands.
+    FragGrenade,
+    /// Marks the ground at the crosshair, then a beam strikes after a delay.
+    OrbitalLance,
+    /// Temporary speed and damage boost.
+    LitanyOfHate,
+}
+
+impl AbilityKind {
+    pub fn get_display_name(&self) -> &str {
+        match self {
+            AbilityKind::FragGrenade => "Frag Grenade",
+            AbilityKind::OrbitalLance => "Orbital Lance",
+            AbilityKind::LitanyOfHate => "Litany of Hate",
+        }
+    }
+
+    fn get_base_cooldown(&self) -> f32 {
+        match self {
+            AbilityKind::FragGrenade => 8.0,
+            AbilityKind::OrbitalLance => 20.0,
+            AbilityKind::LitanyOfHate => 25.0,
+        }
+    }
+}
+
+/// A player-triggered ability and its cooldown.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Ability {
+    pub kind: AbilityKind,
+    pub rank: u32,
+    pub cooldown_remaining: f32,
+}
+
+impl Ability {
+    pub fn new(kind: AbilityKind) -> Self {
+        Ability {
+            kind,
+            rank: 0,
+            cooldown_remaining: 0.0,
+        }
+    }
+
+    pub fn get_cooldown(&self) -> f32 {
+        self.kind.get_base_cooldown() * RANK_COOLDOWN_FACTOR.powi(self.rank as i32)
+    }
+
+    fn get_potency(&self) -> f32 {
+        1.0 + RANK_POTENCY_BONUS * self.rank as f32
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.cooldown_remaining <= 0.0
+    }
+
+    /// 0.0 right after use, 1.0 once it's ready again.
+    pub fn get_cooldown_progress(&self) -> f32 {
+        1.0 - (self.cooldown_remaining / self.get_cooldown()).clamp(0.0, 1.0)
+    }
+
+    pub fn tick(&mut self, delta: f32) {
+        self.cooldown_remaining = (self.cooldown_remaining - delta).max(0.0);
+    }
+
+    /// Starts the cooldown and returns anything the ability spawns. Buffs go
+    /// straight onto `statuses`. Targeted abilities land at `target`, pulled
+    /// in to their max range.
+    pub fn activate(
+        &mut self,
+        origin: Position,
+        target: Position,
+        statuses: &mut Vec<Status>,
+    ) -> Vec<Projectile> {
+        self.cooldown_remaining = self.get_cooldown();
+        let potency = self.get_potency();
+        let dx = target.x - origin.x;
+        let dy = target.y - origin.y;
+        let angle = dy.atan2(dx);
+        let distance = (dx * dx + dy * dy).sqrt();
+
+        match self.kind {
+            AbilityKind::FragGrenade => vec![Projectile::new_frag_grenade(
+                origin,
+                angle,
+                distance.min(GRENADE_MAX_RANGE),
+                GRENADE_RADIUS,
+                (GRENADE_DAMAGE * potency) as i32,
+            )],
+            AbilityKind::OrbitalLance => {
+                let distance = distance.min(LANCE_MAX_RANGE);
+                let position = Position {
+                    x: origin.x + angle.cos() * distance,
+                    y: origin.y + angle.sin() * distance,
+                };
+                vec![Projectile::new_orbital_lance(
+                    position,
+                    LANCE_RADIUS,
+                    (LANCE_DAMAGE * potency) as i32,
+                    LANCE_DELAY,
+                )]
+            }
+            AbilityKind::LitanyOfHate => {
+                let duration = LITANY_DURATION * potency;
+                add_status(
+                    statuses,
+                    Status::SpeedBoost(SpeedBoostStatus {
+                        speed_multiplier: LITANY_SPEED_MULTIPLIER,
+                        remaining_duration: duration,
+                    }),
+                );
+                add_status(
+                    statuses,
+                    Status::DamageBoost(DamageBoostStatus {
+                        damage_multiplier: LITANY_DAMAGE_MULTIPLIER,
+                        remaining_duration: duration,
+                    }),
+                );
+                vec![]
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemy::tests::create_test_player;
+
+    #[test]
+    fn test_ability_goes_on_cooldown() {
+        let mut player = create_test_player(0.0, 0.0);
+        player.abilities = vec![Ability::new(AbilityKind::FragGrenade)];
+
+        assert_eq!(player.use_ability(0).len(), 1);
+        assert!(player.use_ability(0).is_empty(), "Still cooling down");
+
+        let cooldown = player.abilities[0].get_cooldown();
+        player.abilities[0].tick(cooldown);
+        assert!(player.abilities[0].is_ready());
+        assert!(player.use_ability(1).is_empty(), "No second ability");
+    }
+
+    #[test]
+    fn test_litany_buffs_player() {
+        let mut player = create_test_player(0.0, 0.0);
+        player.abilities = vec![Ability::new(AbilityKind::LitanyOfHate)];
+
+        player.use_ability(0);
+
+        assert_eq!(
+            player.calculate_damage_multiplier(),
+            LITANY_DAMAGE_MULTIPLIER
+        );
+        assert!(player
+            .statuses
+            .iter()
+            .any(|status| matches!(status, Status::SpeedBoost(_))));
+    }
+
+    #[test]
+    fn test_rank_shortens_cooldown() {
+        let mut lance = Ability::new(AbilityKind::OrbitalLance);
+        let base = lance.get_cooldown();
+        lance.rank = 2;
+        assert!(lance.get_cooldown() < base);
+        assert!(lance.get_potency() > 1.0);
+    }
+}
@@ -0,0 +1,201 @@
+use raylib::prelude::Color;
+
+use crate::{abilities::AbilityKind, projectiles::Projectile, utils::Position, weapons::Weapon};
+
+// Passive tuning
+const BOLTER_DRILL_RELOAD_SPEED: f32 = 1.25;
+const JUMP_PACK_DASH_CHARGES: i32 = 2;
+const JUMP_PACK_DASH_DURATION: f32 = 0.3; // Regular dashes last 0.18s
+const BRACED_DAMAGE_TAKEN: f32 = 0.75;
+const SERVO_SKULL_COUNT: usize = 2;
+
+/// Picked on the character select screen before a run.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PlayerClass {
+    TacticalMarine,
+    AssaultMarine,
+    Devastator,
+    Techmarine,
+}
+
+pub const ALL_CLASSES: [PlayerClass; 4] = [
+    PlayerClass::TacticalMarine,
+    PlayerClass::AssaultMarine,
+    PlayerClass::Devastator,
+    PlayerClass::Techmarine,
+];
+
+impl PlayerClass {
+    pub fn get_display_name(&self) -> &str {
+        match self {
+            PlayerClass::TacticalMarine => "Tactical Marine",
+            PlayerClass::AssaultMarine => "Assault Marine",
+            PlayerClass::Devastator => "Devastator",
+            PlayerClass::Techmarine => "Techmarine",
+        }
+    }
+
+    pub fn get_passive_name(&self) -> &str {
+        match self {
+            PlayerClass::TacticalMarine => "Bolter Drill",
+            PlayerClass::AssaultMarine => "Jump Pack",
+            PlayerClass::Devastator => "Braced",
+            PlayerClass::Techmarine => "Servo-Skulls",
+        }
+    }
+
+    pub fn get_passive_description(&self) -> &str {
+        match self {
+            PlayerClass::TacticalMarine => "Magazines reload 25% faster.",
+            PlayerClass::AssaultMarine => "Starts with two dash charges, dashes go further.",
+            PlayerClass::Devastator => "Takes 25% less damage.",
+            PlayerClass::Techmarine => "Two servo-skulls orbit and ram nearby enemies.",
+        }
+    }
+
+    pub fn get_max_health(&self) -> i32 {
+        match self {
+            PlayerClass::TacticalMarine => 100,
+            PlayerClass::AssaultMarine => 110,
+            PlayerClass::Devastator => 130,
+            PlayerClass::Techmarine => 90,
+        }
+    }
+
+    pub fn get_armor(&self) -> i32 {
+        match self {
+            PlayerClass::TacticalMarine => 1,
+            PlayerClass::AssaultMarine => 1,
+            PlayerClass::Devastator => 3,
+            PlayerClass::Techmarine => 0,
+        }
+    }
+
+    pub fn get_max_shield(&self) -> f32 {
+        match self {
+            PlayerClass::TacticalMarine => 20.0,
+            PlayerClass::AssaultMarine => 15.0,
+            PlayerClass::Devastator => 20.0,
+            PlayerClass::Techmarine => 40.0,
+        }
+    }
+
+    pub fn get_move_speed(&self) -> f32 {
+        match self {
+            PlayerClass::TacticalMarine => 300.0,
+            PlayerClass::AssaultMarine => 330.0,
+            PlayerClass::Devastator => 250.0,
+            PlayerClass::Techmarine => 300.0,
+        }
+    }
+
+    pub fn get_starting_weapon(&self) -> Weapon {
+        match self {
+            PlayerClass::TacticalMarine | PlayerClass::Techmarine => Weapon::new_bolter(),
+            PlayerClass::AssaultMarine => Weapon::new_power_sword(),
+            PlayerClass::Devastator => Weapon::new_multi_melta(),
+        }
+    }
+
+    pub fn get_abilities(&self) -> Vec<AbilityKind> {
+        match self {
+            PlayerClass::TacticalMarine => vec![AbilityKind::FragGrenade],
+            PlayerClass::AssaultMarine => {
+                vec![AbilityKind::LitanyOfHate, AbilityKind::FragGrenade]
+            }
+            PlayerClass::Devastator => vec![AbilityKind::OrbitalLance],
+            PlayerClass::Techmarine => vec![AbilityKind::OrbitalLance, AbilityKind::FragGrenade],
+        }
+    }
+
+    /// Every class shares the marine sprite, tinted in its own colours.
+    pub fn get_tint(&self) -> Color {
+        match self {
+            PlayerClass::TacticalMarine => Color::WHITE,
+            PlayerClass::AssaultMarine => Color::new(255, 190, 190, 255),
+            PlayerClass::Devastator => Color::new(190, 210, 255, 255),
+            PlayerClass::Techmarine => Color::new(255, 150, 110, 255),
+        }
+    }
+
+    pub fn get_reload_speed(&self) -> f32 {
+        match self {
+            PlayerClass::TacticalMarine => BOLTER_DRILL_RELOAD_SPEED,
+            _ => 1.0,
+        }
+    }
+
+    pub fn get_dash_charges(&self) -> i32 {
+        match self {
+            PlayerClass::AssaultMarine => JUMP_PACK_DASH_CHARGES,
+            _ => 1,
+        }
+    }
+
+    /// `None` keeps the regular dash length.
+    pub fn get_dash_duration(&self) -> Option<f32> {
+        match self {
+            PlayerClass::AssaultMarine => Some(JUMP_PACK_DASH_DURATION),
+            _ => None,
+        }
+    }
+
+    pub fn get_damage_taken_multiplier(&self) -> f32 {
+        match self {
+            PlayerClass::Devastator => BRACED_DAMAGE_TAKEN,
+            _ => 1.0,
+        }
+    }
+
+    /// Projectiles that follow the player for the whole run, spread evenly
+    /// around them.
+    pub fn spawn_companions(&self, position: Position) -> Vec<Projectile> {
+        let count = match self {
+            PlayerClass::Techmarine => SERVO_SKULL_COUNT,
+            _ => 0,
+        };
+        (0..count)
+            .map(|i| {
+                let angle = i as f32 / count as f32 * std::f32::consts::TAU;
+                Projectile::new_servo_skull_companion(position, angle)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{enemy::tests::create_test_player, projectiles::behaviours::Motion};
+
+    #[test]
+    fn test_class_sets_stats_and_kit() {
+        let player = create_test_player(0.0, 0.0).with_class(PlayerClass::Devastator);
+        assert_eq!(player.max_health, 130);
+        assert_eq!(player.health, 130);
+        assert_eq!(player.move_speed, 250.0);
+        assert!(matches!(player.weapons[0], Some(Weapon::MultiMelta(_))));
+
+        let mut player = player;
+        player.shield = 0.0;
+        player.take_damage(20);
+        assert_eq!(player.health, 118, "Braced takes a quarter off, then armor");
+
+        let player = create_test_player(0.0, 0.0).with_class(PlayerClass::AssaultMarine);
+        assert_eq!(player.dash.charges, 2);
+        assert!(matches!(player.weapons[0], Some(Weapon::PowerSword(_))));
+    }
+
+    #[test]
+    fn test_only_techmarine_has_companions() {
+        let position = Position { x: 0.0, y: 0.0 };
+        let skulls = PlayerClass::Techmarine.spawn_companions(position);
+        assert_eq!(skulls.len(), SERVO_SKULL_COUNT);
+        assert!(skulls
+            .iter()
+            .all(|skull| matches!(skull.motion, Motion::Orbit { .. })));
+        assert!(PlayerClass::TacticalMarine
+            .spawn_companions(position)
+            .is_empty());
+    }
+}
@@ -0,0 +1,52 @@
+/// Run-wide difficulty setting.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+pub const ALL_DIFFICULTIES: [Difficulty; 3] =
+    [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+impl Difficulty {
+    pub fn get_display_name(&self) -> &str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    /// One line summary for the difficulty select screen.
+    pub fn get_description(&self) -> &str {
+        match self {
+            Difficulty::Easy => "Long recovery after hits, starts with an Emperor's Blessing",
+            Difficulty::Normal => "The run as intended",
+            Difficulty::Hard => "Short recovery after hits and no last stand",
+        }
+    }
+
+    /// Seconds the player can't be hurt again after taking a hit.
+    pub fn get_hit_invulnerability(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.0,
+            Difficulty::Normal => 0.6,
+            Difficulty::Hard => 0.3,
+        }
+    }
+
+    /// Emperor's Blessings the player starts the run with.
+    pub fn get_starting_blessings(&self) -> i32 {
+        match self {
+            Difficulty::Easy => 1,
+            Difficulty::Normal | Difficulty::Hard => 0,
+        }
+    }
+
+    /// Hard runs end on the first lethal hit, with no last stand.
+    pub fn allows_last_stand(&self) -> bool {
+        !matches!(self, Difficulty::Hard)
+    }
+}
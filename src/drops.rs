@@ -0,0 +1,225 @@
+use rand::prelude::*;
+
+use crate::{
+    player::Player,
+    upgrades::{Upgrade, ALL_UPGRADES},
+    utils::Position,
+};
+
+const DROP_RADIUS: f32 = 32.0;
+const HEALTH_ORB_HEAL: i32 = 20;
+// How quickly magnetised items speed up, in pixels per second squared
+const MAGNET_ACCELERATION: f32 = 1500.0;
+// Long enough for both drops and weapon pickups to notice the vacuum
+const VACUUM_DURATION: f32 = 0.5;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DropKind {
+    /// Left behind by bosses. Grants a random upgrade, or a full heal once
+    /// every upgrade is maxed out.
+    RewardChest,
+    /// Left behind by elites. Heals the player a little.
+    HealthOrb,
+    /// Left behind by bosses. Revives the player the next time they fall.
+    EmperorsBlessing,
+    /// Left behind by elites now and then. Pulls in every drop and pickup on the map.
+    Vacuum,
+}
+
+impl DropKind {
+    pub fn get_display_name(&self) -> &str {
+        match self {
+            DropKind::RewardChest => "Reward Chest",
+            DropKind::HealthOrb => "Health",
+            DropKind::EmperorsBlessing => "Emperor's Blessing",
+            DropKind::Vacuum => "Vacuum",
+        }
+    }
+}
+
+/// Something left on the ground by a dead enemy.
+#[derive(Clone, Copy)]
+pub struct DroppedItem {
+    pub kind: DropKind,
+    pub position: Position,
+    pub radius: f32,
+    /// Zero until the player's magnet catches it.
+    pub speed: f32,
+}
+
+impl DroppedItem {
+    pub fn new(kind: DropKind, position: Position) -> Self {
+        DroppedItem {
+            kind,
+            position,
+            radius: DROP_RADIUS,
+            speed: 0.0,
+        }
+    }
+
+    fn apply(&self, player: &mut Player, rng: &mut impl Rng) {
+        match self.kind {
+            DropKind::RewardChest => {
+                let available: Vec<Upgrade> = ALL_UPGRADES
+                    .iter()
+                    .filter(|upgrade| upgrade.can_apply(player))
+                    .copied()
+                    .collect();
+                match available.choose(rng) {
+                    Some(upgrade) => upgrade.apply(player),
+                    None => player.health = player.max_health,
+                }
+            }
+            DropKind::HealthOrb => {
+                player.health = (player.health + HEALTH_ORB_HEAL).min(player.max_health);
+            }
+            DropKind::EmperorsBlessing => player.blessings += 1,
+            DropKind::Vacuum => player.vacuum_remaining = VACUUM_DURATION,
+        }
+    }
+}
+
+pub struct AllDrops {
+    pub drops: Vec<DroppedItem>,
+}
+
+impl AllDrops {
+    pub fn new() -> Self {
+        AllDrops { drops: vec![] }
+    }
+
+    pub fn append(&mut self, drops: &mut Vec<DroppedItem>) {
+        self.drops.append(drops);
+    }
+
+    /// Pulls in anything the magnet has caught and hands over anything the
+    /// player is touching.
+    pub fn update(&mut self, player: &mut Player, delta: &f32) {
+        let mut rng = rand::rng();
+        for drop in self.drops.iter_mut() {
+            apply_magnet(&mut drop.position, &mut drop.speed, player, *delta);
+        }
+        self.drops.retain(|drop| {
+            let dx = player.position.x - drop.position.x;
+            let dy = player.position.y - drop.position.y;
+            let radius = player.collision_radius + drop.radius;
+            if dx * dx + dy * dy > radius * radius {
+                return true;
+            }
+            drop.apply(player, &mut rng);
+            false
+        });
+    }
+}
+
+impl Default for AllDrops {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Moves an item toward the player once it's inside their magnet radius, or
+/// from anywhere while a vacuum is active. Once caught it keeps coming and
+/// keeps speeding up, so the player can't outrun it.
+pub fn apply_magnet(position: &mut Position, speed: &mut f32, player: &Player, delta: f32) {
+    let dx = player.position.x - position.x;
+    let dy = player.position.y - position.y;
+    let distance = (dx * dx + dy * dy).sqrt();
+    let caught = *speed > 0.0 || player.is_vacuuming() || distance <= player.magnet_radius;
+    if !caught || distance <= 0.0 {
+        return;
+    }
+
+    *speed += MAGNET_ACCELERATION * delta;
+    let step = (*speed * delta).min(distance);
+    position.x += dx / distance * step;
+    position.y += dy / distance * step;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemy::tests::create_test_player;
+
+    #[test]
+    fn test_reward_chest_is_picked_up_on_contact() {
+        let mut player = create_test_player(0.0, 0.0);
+        let mut drops = AllDrops::new();
+        drops.append(&mut vec![
+            DroppedItem::new(DropKind::RewardChest, Position { x: 10.0, y: 0.0 }),
+            DroppedItem::new(DropKind::RewardChest, Position { x: 500.0, y: 0.0 }),
+        ]);
+
+        drops.update(&mut player, &0.0);
+
+        assert_eq!(drops.drops.len(), 1, "Only the nearby chest is collected");
+        let upgraded = player.ricochet_bounces > 0
+            || player.reload_speed > 1.0
+            || player.dash.max_charges > 1
+            || player.abilities.iter().any(|ability| ability.rank > 0);
+        assert!(upgraded, "Chest should grant an upgrade");
+    }
+
+    #[test]
+    fn test_emperors_blessing_is_banked() {
+        let mut player = create_test_player(0.0, 0.0);
+        let blessings = player.blessings;
+        let mut drops = AllDrops::new();
+        drops.append(&mut vec![DroppedItem::new(
+            DropKind::EmperorsBlessing,
+            Position { x: 0.0, y: 10.0 },
+        )]);
+
+        drops.update(&mut player, &0.0);
+
+        assert!(drops.drops.is_empty());
+        assert_eq!(player.blessings, blessings + 1);
+    }
+
+    #[test]
+    fn test_magnet_pulls_nearby_drops_in() {
+        let mut player = create_test_player(0.0, 0.0);
+        let mut drops = AllDrops::new();
+        drops.append(&mut vec![
+            DroppedItem::new(DropKind::HealthOrb, Position { x: 120.0, y: 0.0 }),
+            DroppedItem::new(DropKind::HealthOrb, Position { x: 1000.0, y: 0.0 }),
+        ]);
+
+        drops.update(&mut player, &0.1);
+        assert!(
+            drops.drops[0].position.x < 120.0,
+            "Inside the magnet radius"
+        );
+        assert_eq!(
+            drops.drops[1].position.x, 1000.0,
+            "Outside the magnet radius"
+        );
+
+        for _ in 0..10 {
+            drops.update(&mut player, &0.1);
+        }
+        assert_eq!(drops.drops.len(), 1, "The nearby orb reaches the player");
+        assert_eq!(drops.drops[0].position.x, 1000.0);
+    }
+
+    #[test]
+    fn test_vacuum_pulls_every_drop() {
+        let mut player = create_test_player(0.0, 0.0);
+        let mut drops = AllDrops::new();
+        drops.append(&mut vec![
+            DroppedItem::new(DropKind::Vacuum, Position { x: 10.0, y: 0.0 }),
+            DroppedItem::new(DropKind::HealthOrb, Position { x: 2000.0, y: 0.0 }),
+        ]);
+
+        drops.update(&mut player, &0.1);
+        assert!(player.is_vacuuming());
+        drops.update(&mut player, &0.1);
+        let pulled_to = drops.drops[0].position.x;
+        assert!(pulled_to < 2000.0);
+
+        // Keeps coming after the vacuum wears off
+        player.vacuum_remaining = 0.0;
+        drops.update(&mut player, &0.1);
+        assert!(drops.drops[0].position.x < pulled_to);
+    }
+}
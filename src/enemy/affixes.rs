@@ -0,0 +1,242 @@
+use rand::prelude::*;
+use raylib::prelude::Color;
+
+use crate::{
+    drops::DropKind,
+    enemy::{DeathEffect, Enemy},
+    player::Player,
+    statuses::{RegenerationStatus, Status},
+    utils::Position,
+};
+
+// Elites get more common, and pick up more affixes, as the run goes on
+const ELITE_BASE_CHANCE: f64 = 0.02;
+const ELITE_MAX_CHANCE: f64 = 0.2;
+const ELITE_CHANCE_SCALING: f32 = 600.0; // Seconds to reach the max chance
+const EXTRA_AFFIX_INTERVAL: f32 = 300.0; // One more affix every 5 minutes
+const MAX_AFFIXES: usize = 3;
+// Affix strength grows by this much per minute
+const POTENCY_PER_MINUTE: f32 = 0.1;
+
+const SHIELD_FRACTION: f32 = 0.5; // Of max health
+const HASTE_SPEED_MULTIPLIER: f32 = 1.5;
+const HASTE_ATTACK_MULTIPLIER: f32 = 0.7;
+const VAMPIRIC_HEAL_FRACTION: f32 = 1.0; // Of contact damage dealt
+const REGEN_FRACTION_PER_SECOND: f32 = 0.05; // Of max health
+const TELEPORT_INTERVAL: f32 = 4.0;
+const TELEPORT_MIN_DISTANCE: f32 = 600.0; // Only blinks when further than this
+const TELEPORT_LANDING_DISTANCE: f32 = 250.0;
+// Each affix adds this chance of dropping a reward chest instead of a health orb
+const CHEST_CHANCE_PER_AFFIX: f64 = 0.1;
+const VACUUM_CHANCE: f64 = 0.15;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Affix {
+    /// Extra pool of health that soaks damage first.
+    Shielded,
+    /// Moves and attacks faster.
+    Hasted,
+    /// Heals from contact damage dealt to the player.
+    Vampiric,
+    /// Blows up on death.
+    Exploding,
+    /// Slowly heals back to full.
+    Regenerating,
+    /// Blinks next to the player when left behind.
+    Teleporting,
+}
+
+pub const ALL_AFFIXES: [Affix; 6] = [
+    Affix::Shielded,
+    Affix::Hasted,
+    Affix::Vampiric,
+    Affix::Exploding,
+    Affix::Regenerating,
+    Affix::Teleporting,
+];
+
+impl Affix {
+    pub fn get_display_name(&self) -> &str {
+        match self {
+            Affix::Shielded => "Shielded",
+            Affix::Hasted => "Hasted",
+            Affix::Vampiric => "Vampiric",
+            Affix::Exploding => "Exploding",
+            Affix::Regenerating => "Regenerating",
+            Affix::Teleporting => "Teleporting",
+        }
+    }
+
+    /// Outline colour the renderer draws around elites.
+    pub fn get_color(&self) -> Color {
+        match self {
+            Affix::Shielded => Color::SKYBLUE,
+            Affix::Hasted => Color::YELLOW,
+            Affix::Vampiric => Color::MAROON,
+            Affix::Exploding => Color::ORANGE,
+            Affix::Regenerating => Color::LIME,
+            Affix::Teleporting => Color::VIOLET,
+        }
+    }
+}
+
+/// Affix state carried by an elite enemy.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Elite {
+    pub affixes: Vec<Affix>,
+    pub shield: i32,
+    pub max_shield: i32,
+    pub time_until_teleport: f32,
+}
+
+impl Elite {
+    pub fn has_affix(&self, affix: Affix) -> bool {
+        self.affixes.contains(&affix)
+    }
+}
+
+/// Chance that a regular spawn rolls as an elite at this point in the run.
+pub fn get_elite_chance(elapsed_time: f32) -> f64 {
+    let ramp = (elapsed_time / ELITE_CHANCE_SCALING).min(1.0) as f64;
+    ELITE_BASE_CHANCE + (ELITE_MAX_CHANCE - ELITE_BASE_CHANCE) * ramp
+}
+
+/// Possibly turns a freshly spawned enemy into an elite.
+pub fn roll_elite(enemy: &mut Enemy, elapsed_time: f32, rng: &mut impl Rng) {
+    if !rng.random_bool(get_elite_chance(elapsed_time)) {
+        return;
+    }
+    let count = (1 + (elapsed_time / EXTRA_AFFIX_INTERVAL) as usize).min(MAX_AFFIXES);
+    let affixes = ALL_AFFIXES.choose_multiple(rng, count).copied().collect();
+    make_elite(enemy, affixes, elapsed_time, rng);
+}
+
+/// Applies `affixes` to the enemy, scaled by how far into the run it is.
+pub fn make_elite(enemy: &mut Enemy, affixes: Vec<Affix>, elapsed_time: f32, rng: &mut impl Rng) {
+    let potency = 1.0 + elapsed_time / 60.0 * POTENCY_PER_MINUTE;
+    let mut elite = Elite {
+        affixes,
+        shield: 0,
+        max_shield: 0,
+        time_until_teleport: TELEPORT_INTERVAL,
+    };
+
+    for affix in elite.affixes.iter() {
+        match affix {
+            Affix::Shielded => {
+                elite.max_shield = (enemy.max_health as f32 * SHIELD_FRACTION * potency) as i32;
+                elite.shield = elite.max_shield;
+            }
+            Affix::Hasted => {
+                enemy.speed = (enemy.speed as f32 * HASTE_SPEED_MULTIPLIER) as i32;
+                enemy.attack_speed *= HASTE_ATTACK_MULTIPLIER;
+            }
+            Affix::Exploding => enemy.on_death.push(DeathEffect::Explode {
+                radius: 120.0,
+                player_damage: (15.0 * potency) as i32,
+                enemy_damage: 0,
+            }),
+            Affix::Regenerating => {
+                let heal = enemy.max_health as f32 * REGEN_FRACTION_PER_SECOND * potency;
+                enemy.add_status(Status::Regeneration(RegenerationStatus {
+                    heal_per_tick: (heal as i32).max(1),
+                    tick_interval: 1.0,
+                    remaining_duration: f32::INFINITY,
+                    time_since_last_tick: 0.0,
+                }));
+            }
+            Affix::Vampiric | Affix::Teleporting => {}
+        }
+    }
+
+    // Tougher elites are more likely to leave a chest behind
+    let chest_chance = CHEST_CHANCE_PER_AFFIX * elite.affixes.len() as f64;
+    let reward = if rng.random_bool(chest_chance.min(1.0)) {
+        DropKind::RewardChest
+    } else if rng.random_bool(VACUUM_CHANCE) {
+        DropKind::Vacuum
+    } else {
+        DropKind::HealthOrb
+    };
+    enemy.on_death.push(DeathEffect::Drop(reward));
+    enemy.elite = Some(elite);
+}
+
+/// Per-frame affix behaviour that isn't covered by stats or statuses.
+pub fn handle_elite(player: &Player, enemy: &mut Enemy, delta: &f32) {
+    let Some(elite) = enemy.elite.as_mut() else {
+        return;
+    };
+    if !elite.has_affix(Affix::Teleporting) {
+        return;
+    }
+
+    elite.time_until_teleport -= delta;
+    let dx = enemy.position.x - player.position.x;
+    let dy = enemy.position.y - player.position.y;
+    let distance = (dx * dx + dy * dy).sqrt();
+    if elite.time_until_teleport > 0.0 || distance <= TELEPORT_MIN_DISTANCE {
+        return;
+    }
+
+    // Land on the same side of the player it was already on
+    enemy.position = Position {
+        x: player.position.x + dx / distance * TELEPORT_LANDING_DISTANCE,
+        y: player.position.y + dy / distance * TELEPORT_LANDING_DISTANCE,
+    };
+    enemy.velocity_x = 0.0;
+    enemy.velocity_y = 0.0;
+    elite.time_until_teleport = TELEPORT_INTERVAL;
+}
+
+/// Heals a vampiric elite for the contact damage it just dealt.
+pub fn handle_contact_hit(enemy: &mut Enemy, damage_dealt: i32) {
+    if enemy
+        .elite
+        .as_ref()
+        .is_some_and(|elite| elite.has_affix(Affix::Vampiric))
+    {
+        let heal = (damage_dealt as f32 * VAMPIRIC_HEAL_FRACTION) as i32;
+        enemy.health = (enemy.health + heal).min(enemy.max_health);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemy::{tests::create_test_player, EnemyType};
+
+    #[test]
+    fn test_elite_chance_scales_with_time() {
+        assert_eq!(get_elite_chance(0.0), ELITE_BASE_CHANCE);
+        assert!(get_elite_chance(300.0) > get_elite_chance(60.0));
+        assert_eq!(get_elite_chance(10_000.0), ELITE_MAX_CHANCE);
+    }
+
+    #[test]
+    fn test_shield_soaks_damage_before_health() {
+        let mut enemy = EnemyType::new_dark_fighter(Position { x: 0.0, y: 0.0 });
+        make_elite(&mut enemy, vec![Affix::Shielded], 0.0, &mut rand::rng());
+        assert_eq!(enemy.elite.as_ref().unwrap().shield, 20);
+
+        enemy.take_damage(15, false);
+        assert_eq!(enemy.health, 40);
+        assert_eq!(enemy.elite.as_ref().unwrap().shield, 5);
+
+        enemy.take_damage(15, false);
+        assert_eq!(enemy.health, 30, "Overflow goes through to health");
+        assert_eq!(enemy.elite.as_ref().unwrap().shield, 0);
+    }
+
+    #[test]
+    fn test_teleporting_elite_blinks_to_player() {
+        let player = create_test_player(0.0, 0.0);
+        let mut enemy = EnemyType::new_servo_skull(Position { x: 2000.0, y: 0.0 });
+        make_elite(&mut enemy, vec![Affix::Teleporting], 0.0, &mut rand::rng());
+
+        handle_elite(&player, &mut enemy, &(TELEPORT_INTERVAL + 0.1));
+
+        assert_eq!(enemy.position.x, TELEPORT_LANDING_DISTANCE);
+        assert_eq!(enemy.position.y, 0.0);
+    }
+}
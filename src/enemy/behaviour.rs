@@ -0,0 +1,542 @@
+use rand::prelude::*;
+
+use crate::{player::Player, utils::Position};
+
+// Wander tuning
+const WANDER_TURN_INTERVAL: f32 = 2.0; // Seconds between picking a new heading
+const WANDER_SPEED_SCALE: f32 = 0.4;
+
+// Keep distance tuning
+const STRAFE_SWITCH_INTERVAL: f32 = 3.0; // Seconds before circling the other way
+
+// Charge tuning
+const CHARGE_RANGE: f32 = 350.0; // Starts winding up once this close
+const CHARGE_WIND_UP: f32 = 0.6;
+const CHARGE_DASH_TIME: f32 = 0.35;
+const CHARGE_DASH_SPEED_SCALE: f32 = 3.0;
+const CHARGE_RECOVER_TIME: f32 = 0.8;
+
+// Fuse tuning
+const FUSE_RANGE: f32 = 120.0; // Stops and lights the fuse once this close
+const FUSE_TIME: f32 = 0.8;
+const RUSH_SPEED_SCALE: f32 = 1.3;
+
+// Swarm tuning
+const SWARM_RING_RADIUS: f32 = 200.0; // Swarmers gather on a ring this far from the player
+const SWARM_COLLAPSE_FACTOR: f32 = 1.5; // Rush in once this close to the ring
+
+// Formation tuning
+const ENCIRCLE_SHRINK_RATE: f32 = 90.0; // Pixels per second the ring closes by
+const ENCIRCLE_BREAK_RADIUS: f32 = 150.0; // Ring breaks up into normal behaviour here
+const SWEEP_TIME: f32 = 10.0; // Long enough to cross the whole screen
+
+/// Where an enemy wants to go this frame.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Steering {
+    pub direction_x: f32,
+    pub direction_y: f32,
+    /// Scales both acceleration and top speed. 0.0 brakes to a stop.
+    pub speed_scale: f32,
+    /// Set by dashes that are meant to outrun the usual velocity cap.
+    pub ignores_speed_cap: bool,
+}
+
+impl Steering {
+    fn towards(dx: f32, dy: f32, speed_scale: f32) -> Self {
+        let length = (dx * dx + dy * dy).sqrt().max(0.001);
+        Steering {
+            direction_x: dx / length,
+            direction_y: dy / length,
+            speed_scale,
+            ignores_speed_cap: false,
+        }
+    }
+
+    fn along(angle: f32, speed_scale: f32) -> Self {
+        Steering {
+            direction_x: angle.cos(),
+            direction_y: angle.sin(),
+            speed_scale,
+            ignores_speed_cap: false,
+        }
+    }
+
+    /// Like `along`, but allowed to go past the velocity cap.
+    fn dash(angle: f32, speed_scale: f32) -> Self {
+        Steering {
+            ignores_speed_cap: true,
+            ..Steering::along(angle, speed_scale)
+        }
+    }
+
+    fn brake() -> Self {
+        Steering {
+            direction_x: 0.0,
+            direction_y: 0.0,
+            speed_scale: 0.0,
+            ignores_speed_cap: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ChargePhase {
+    /// Closing in until within charge range.
+    Approach,
+    /// Stopped and telegraphing; the dash angle is locked in.
+    WindUp {
+        remaining: f32,
+        angle: f32,
+    },
+    Dash {
+        remaining: f32,
+        angle: f32,
+    },
+    /// Catching its breath after a dash.
+    Recover {
+        remaining: f32,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FusePhase {
+    /// Rushing the player faster than a normal chase.
+    Rush,
+    /// Stopped and flashing until it blows.
+    Lit {
+        remaining: f32,
+    },
+    Detonated,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BehaviourState {
+    /// Drifting around until the player comes into aggro range.
+    Wander {
+        heading: f32,
+        time_until_turn: f32,
+    },
+    Chase,
+    /// Holds `preferred_distance` from the player while circling them.
+    KeepDistance {
+        preferred_distance: f32,
+        strafe_sign: f32,
+        time_until_switch: f32,
+    },
+    Charge(ChargePhase),
+    /// Rushes the player, then stops and detonates.
+    Fuse(FusePhase),
+    /// Running away from the player.
+    Flee,
+    /// Gathers on a ring around the player at `flank_angle`, then rushes in.
+    Swarm {
+        flank_angle: f32,
+    },
+    /// Holds `angle` on a ring around the player that shrinks over time.
+    /// Breaks back to the primary state once the ring is tight.
+    Encircle {
+        angle: f32,
+        radius: f32,
+    },
+    /// Flies along `angle` regardless of the player, as part of a wall
+    /// sweeping across the screen, then returns to the primary state.
+    Sweep {
+        angle: f32,
+        remaining: f32,
+    },
+}
+
+impl BehaviourState {
+    pub fn keep_distance(preferred_distance: f32) -> Self {
+        BehaviourState::KeepDistance {
+            preferred_distance,
+            strafe_sign: 1.0,
+            time_until_switch: STRAFE_SWITCH_INTERVAL,
+        }
+    }
+
+    pub fn charge() -> Self {
+        BehaviourState::Charge(ChargePhase::Approach)
+    }
+
+    pub fn fuse() -> Self {
+        BehaviourState::Fuse(FusePhase::Rush)
+    }
+
+    pub fn sweep(angle: f32) -> Self {
+        BehaviourState::Sweep {
+            angle,
+            remaining: SWEEP_TIME,
+        }
+    }
+
+    pub fn swarm(rng: &mut impl Rng) -> Self {
+        BehaviourState::Swarm {
+            flank_angle: rng.random_range(-std::f32::consts::PI..std::f32::consts::PI),
+        }
+    }
+}
+
+/// Per-enemy state machine. `primary` is the state the enemy returns to
+/// whenever it isn't wandering or fleeing.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Behaviour {
+    pub state: BehaviourState,
+    pub primary: BehaviourState,
+    /// Wanders until the player is within this range. `None` is always aggressive.
+    pub aggro_range: Option<f32>,
+    /// Flees once health drops below this fraction of max health.
+    pub flee_below: Option<f32>,
+}
+
+impl Behaviour {
+    pub fn new(primary: BehaviourState) -> Self {
+        Behaviour {
+            state: primary,
+            primary,
+            aggro_range: None,
+            flee_below: None,
+        }
+    }
+
+    pub fn with_aggro_range(mut self, aggro_range: f32) -> Self {
+        self.aggro_range = Some(aggro_range);
+        self.state = BehaviourState::Wander {
+            heading: 0.0,
+            time_until_turn: 0.0,
+        };
+        self
+    }
+
+    pub fn with_flee_below(mut self, health_fraction: f32) -> Self {
+        self.flee_below = Some(health_fraction);
+        self
+    }
+
+    /// Ranged attacks are held while wandering or fleeing.
+    pub fn can_attack(&self) -> bool {
+        !matches!(
+            self.state,
+            BehaviourState::Wander { .. } | BehaviourState::Flee
+        )
+    }
+
+    /// Locked dash angle and wind-up progress (0.0 to 1.0) while telegraphing a charge.
+    pub fn get_charge_telegraph(&self) -> Option<(f32, f32)> {
+        match self.state {
+            BehaviourState::Charge(ChargePhase::WindUp { remaining, angle }) => {
+                Some((angle, 1.0 - (remaining / CHARGE_WIND_UP).clamp(0.0, 1.0)))
+            }
+            _ => None,
+        }
+    }
+
+    /// 0.0 when the fuse is lit, 1.0 when it blows.
+    pub fn get_fuse_progress(&self) -> Option<f32> {
+        match self.state {
+            BehaviourState::Fuse(FusePhase::Lit { remaining }) => {
+                Some(1.0 - (remaining / FUSE_TIME).clamp(0.0, 1.0))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn has_detonated(&self) -> bool {
+        self.state == BehaviourState::Fuse(FusePhase::Detonated)
+    }
+
+    pub fn update(
+        &mut self,
+        player: &Player,
+        position: &Position,
+        health_fraction: f32,
+        delta: f32,
+    ) -> Steering {
+        let dx = player.position.x - position.x;
+        let dy = player.position.y - position.y;
+        let distance = (dx * dx + dy * dy).sqrt();
+
+        self.transition(distance, health_fraction);
+        self.steer(dx, dy, distance, delta)
+    }
+
+    fn transition(&mut self, distance: f32, health_fraction: f32) {
+        if self
+            .flee_below
+            .is_some_and(|threshold| health_fraction < threshold)
+        {
+            self.state = BehaviourState::Flee;
+            return;
+        }
+
+        match self.state {
+            BehaviourState::Wander { .. }
+                if self.aggro_range.is_none_or(|range| distance <= range) =>
+            {
+                self.state = self.primary;
+            }
+            // Health is back above the threshold (e.g. from regeneration)
+            BehaviourState::Flee => self.state = self.primary,
+            // Formation moves are temporary
+            BehaviourState::Encircle { radius, .. } if radius <= ENCIRCLE_BREAK_RADIUS => {
+                self.state = self.primary;
+            }
+            BehaviourState::Sweep { remaining, .. } if remaining <= 0.0 => {
+                self.state = self.primary;
+            }
+            _ => {}
+        }
+    }
+
+    fn steer(&mut self, dx: f32, dy: f32, distance: f32, delta: f32) -> Steering {
+        match &mut self.state {
+            BehaviourState::Wander {
+                heading,
+                time_until_turn,
+            } => {
+                *time_until_turn -= delta;
+                if *time_until_turn <= 0.0 {
+                    *heading = rand::random_range(-std::f32::consts::PI..std::f32::consts::PI);
+                    *time_until_turn = WANDER_TURN_INTERVAL;
+                }
+                Steering::along(*heading, WANDER_SPEED_SCALE)
+            }
+            BehaviourState::Chase => Steering::towards(dx, dy, 1.0),
+            BehaviourState::KeepDistance {
+                preferred_distance,
+                strafe_sign,
+                time_until_switch,
+            } => {
+                *time_until_switch -= delta;
+                if *time_until_switch <= 0.0 {
+                    *strafe_sign = -*strafe_sign;
+                    *time_until_switch = STRAFE_SWITCH_INTERVAL;
+                }
+
+                let radial =
+                    ((distance - *preferred_distance) / *preferred_distance).clamp(-1.0, 1.0);
+                let to_player = Steering::towards(dx, dy, 1.0);
+                let x = to_player.direction_x * radial - to_player.direction_y * *strafe_sign;
+                let y = to_player.direction_y * radial + to_player.direction_x * *strafe_sign;
+                Steering::towards(x, y, 1.0)
+            }
+            BehaviourState::Charge(phase) => steer_charge(phase, dx, dy, distance, delta),
+            BehaviourState::Fuse(phase) => steer_fuse(phase, dx, dy, distance, delta),
+            BehaviourState::Flee => Steering::towards(-dx, -dy, 1.0),
+            BehaviourState::Swarm { flank_angle } => {
+                if distance <= SWARM_RING_RADIUS * SWARM_COLLAPSE_FACTOR {
+                    return Steering::towards(dx, dy, 1.0);
+                }
+                // Head for our spot on the ring rather than straight at the player
+                let ring_x = dx + flank_angle.cos() * SWARM_RING_RADIUS;
+                let ring_y = dy + flank_angle.sin() * SWARM_RING_RADIUS;
+                Steering::towards(ring_x, ring_y, 1.0)
+            }
+            BehaviourState::Encircle { angle, radius } => {
+                *radius = (*radius - ENCIRCLE_SHRINK_RATE * delta).max(0.0);
+                // Spot on the ring, relative to the enemy
+                let ring_x = dx + angle.cos() * *radius;
+                let ring_y = dy + angle.sin() * *radius;
+                Steering::towards(ring_x, ring_y, 1.0)
+            }
+            BehaviourState::Sweep { angle, remaining } => {
+                *remaining -= delta;
+                Steering::along(*angle, 1.0)
+            }
+        }
+    }
+}
+
+fn steer_charge(phase: &mut ChargePhase, dx: f32, dy: f32, distance: f32, delta: f32) -> Steering {
+    match phase {
+        ChargePhase::Approach => {
+            if distance <= CHARGE_RANGE {
+                *phase = ChargePhase::WindUp {
+                    remaining: CHARGE_WIND_UP,
+                    angle: dy.atan2(dx),
+                };
+                return Steering::brake();
+            }
+            Steering::towards(dx, dy, 1.0)
+        }
+        ChargePhase::WindUp { remaining, angle } => {
+            *remaining -= delta;
+            if *remaining <= 0.0 {
+                let angle = *angle;
+                *phase = ChargePhase::Dash {
+                    remaining: CHARGE_DASH_TIME,
+                    angle,
+                };
+                return Steering::dash(angle, CHARGE_DASH_SPEED_SCALE);
+            }
+            Steering::brake()
+        }
+        ChargePhase::Dash { remaining, angle } => {
+            *remaining -= delta;
+            let angle = *angle;
+            if *remaining <= 0.0 {
+                *phase = ChargePhase::Recover {
+                    remaining: CHARGE_RECOVER_TIME,
+                };
+            }
+            Steering::dash(angle, CHARGE_DASH_SPEED_SCALE)
+        }
+        ChargePhase::Recover { remaining } => {
+            *remaining -= delta;
+            if *remaining <= 0.0 {
+                *phase = ChargePhase::Approach;
+            }
+            Steering::brake()
+        }
+    }
+}
+
+fn steer_fuse(phase: &mut FusePhase, dx: f32, dy: f32, distance: f32, delta: f32) -> Steering {
+    match phase {
+        FusePhase::Rush => {
+            if distance <= FUSE_RANGE {
+                *phase = FusePhase::Lit {
+                    remaining: FUSE_TIME,
+                };
+                return Steering::brake();
+            }
+            Steering::towards(dx, dy, RUSH_SPEED_SCALE)
+        }
+        FusePhase::Lit { remaining } => {
+            *remaining -= delta;
+            if *remaining <= 0.0 {
+                *phase = FusePhase::Detonated;
+            }
+            Steering::brake()
+        }
+        FusePhase::Detonated => Steering::brake(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemy::tests::create_test_player;
+
+    #[test]
+    fn test_chase_steers_towards_player() {
+        let player = create_test_player(100.0, 0.0);
+        let mut behaviour = Behaviour::new(BehaviourState::Chase);
+
+        let steering = behaviour.update(&player, &Position { x: 0.0, y: 0.0 }, 1.0, 0.1);
+
+        assert!((steering.direction_x - 1.0).abs() < 0.001);
+        assert!(steering.direction_y.abs() < 0.001);
+    }
+
+    #[test]
+    fn test_wander_until_player_in_aggro_range() {
+        let player = create_test_player(1000.0, 0.0);
+        let mut behaviour = Behaviour::new(BehaviourState::Chase).with_aggro_range(500.0);
+
+        behaviour.update(&player, &Position { x: 0.0, y: 0.0 }, 1.0, 0.1);
+        assert!(matches!(behaviour.state, BehaviourState::Wander { .. }));
+
+        behaviour.update(&player, &Position { x: 600.0, y: 0.0 }, 1.0, 0.1);
+        assert_eq!(behaviour.state, BehaviourState::Chase);
+    }
+
+    #[test]
+    fn test_charge_winds_up_then_dashes() {
+        let player = create_test_player(200.0, 0.0);
+        let mut behaviour = Behaviour::new(BehaviourState::charge());
+        let position = Position { x: 0.0, y: 0.0 };
+
+        // In range, so the charge starts winding up and the enemy stops
+        let steering = behaviour.update(&player, &position, 1.0, 0.1);
+        assert_eq!(steering.speed_scale, 0.0);
+        assert!(behaviour.get_charge_telegraph().is_some());
+
+        // After the wind-up it dashes along the locked angle
+        let steering = behaviour.update(&player, &position, 1.0, CHARGE_WIND_UP);
+        assert_eq!(steering.speed_scale, CHARGE_DASH_SPEED_SCALE);
+        assert!(steering.ignores_speed_cap);
+        assert!((steering.direction_x - 1.0).abs() < 0.001);
+        assert!(matches!(
+            behaviour.state,
+            BehaviourState::Charge(ChargePhase::Dash { .. })
+        ));
+    }
+
+    #[test]
+    fn test_fuse_lights_in_range_then_detonates() {
+        let player = create_test_player(100.0, 0.0);
+        let mut behaviour = Behaviour::new(BehaviourState::fuse());
+        let position = Position { x: 0.0, y: 0.0 };
+
+        behaviour.update(&player, &position, 1.0, 0.1);
+        assert!(behaviour.get_fuse_progress().is_some());
+        assert!(!behaviour.has_detonated());
+
+        behaviour.update(&player, &position, 1.0, FUSE_TIME);
+        assert!(behaviour.has_detonated());
+    }
+
+    #[test]
+    fn test_flee_at_low_health() {
+        let player = create_test_player(100.0, 0.0);
+        let mut behaviour = Behaviour::new(BehaviourState::Chase).with_flee_below(0.25);
+
+        let steering = behaviour.update(&player, &Position { x: 0.0, y: 0.0 }, 0.2, 0.1);
+
+        assert_eq!(behaviour.state, BehaviourState::Flee);
+        assert!(!behaviour.can_attack());
+        assert!(steering.direction_x < 0.0, "Should be running away");
+    }
+
+    #[test]
+    fn test_swarm_gathers_on_ring_before_rushing_in() {
+        let player = create_test_player(0.0, 0.0);
+        let mut behaviour = Behaviour::new(BehaviourState::Swarm { flank_angle: 0.0 });
+
+        // Far away below the player: heads for the ring point to the player's right
+        let far = behaviour.update(&player, &Position { x: 0.0, y: 1000.0 }, 1.0, 0.1);
+        assert!(far.direction_x > 0.0);
+
+        // Close in: rushes straight at the player
+        let close = behaviour.update(&player, &Position { x: 0.0, y: 100.0 }, 1.0, 0.1);
+        assert!(close.direction_x.abs() < 0.001);
+        assert!(close.direction_y < 0.0);
+    }
+
+    #[test]
+    fn test_encircle_closes_in_then_breaks_to_primary() {
+        let player = create_test_player(0.0, 0.0);
+        let mut behaviour = Behaviour::new(BehaviourState::Chase);
+        behaviour.state = BehaviourState::Encircle {
+            angle: 0.0,
+            radius: 500.0,
+        };
+
+        // Sat on its spot to the right, the ring shrinking pulls it left
+        let steering = behaviour.update(&player, &Position { x: 500.0, y: 0.0 }, 1.0, 1.0);
+        assert!(steering.direction_x < 0.0);
+        assert!(steering.direction_y.abs() < 0.001);
+
+        behaviour.update(&player, &Position { x: 400.0, y: 0.0 }, 1.0, 10.0);
+        behaviour.update(&player, &Position { x: 100.0, y: 0.0 }, 1.0, 0.1);
+        assert_eq!(behaviour.state, BehaviourState::Chase);
+    }
+
+    #[test]
+    fn test_sweep_ignores_player_until_done() {
+        let player = create_test_player(0.0, 500.0);
+        let mut behaviour = Behaviour::new(BehaviourState::keep_distance(300.0));
+        behaviour.state = BehaviourState::sweep(0.0);
+
+        let steering = behaviour.update(&player, &Position { x: 0.0, y: 0.0 }, 1.0, 1.0);
+        assert!((steering.direction_x - 1.0).abs() < 0.001);
+
+        behaviour.update(&player, &Position { x: 0.0, y: 0.0 }, 1.0, SWEEP_TIME);
+        behaviour.update(&player, &Position { x: 0.0, y: 0.0 }, 1.0, 0.1);
+        assert!(matches!(
+            behaviour.state,
+            BehaviourState::KeepDistance { .. }
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::enemy::behaviour::{Behaviour, BehaviourState};
+
+// Later bosses attack this much faster per tier
+const TIER_ATTACK_SPEEDUP: f32 = 0.85;
+
+const BOSS_NAMES: [&str; 3] = ["Iron Behemoth", "Crimson Behemoth", "Behemoth Prime"];
+
+/// What a boss does every time its attack timer runs out.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BossAttack {
+    /// Fires `count` bolts evenly spaced around the boss.
+    BulletRing { count: u32 },
+    /// Calls in `count` servo skulls around the boss.
+    SummonMinions { count: u32 },
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BossPhase {
+    /// The phase starts once health falls below this fraction of max health.
+    pub health_threshold: f32,
+    pub movement: BehaviourState,
+    pub attack: BossAttack,
+    pub attack_interval: f32,
+}
+
+/// Phase tracking for a boss. Phases only ever move forward, even if the boss
+/// heals back above a threshold.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Boss {
+    pub name: &'static str,
+    pub phases: Vec<BossPhase>,
+    pub phase_index: usize,
+    pub time_since_attack: f32,
+}
+
+impl Boss {
+    /// `tier` is how many bosses have already spawned this run.
+    pub fn new(tier: usize) -> Self {
+        let speedup = TIER_ATTACK_SPEEDUP.powi(tier as i32);
+        Boss {
+            name: BOSS_NAMES[tier.min(BOSS_NAMES.len() - 1)],
+            phases: vec![
+                BossPhase {
+                    health_threshold: 1.0,
+                    movement: BehaviourState::Chase,
+                    attack: BossAttack::BulletRing { count: 12 },
+                    attack_interval: 2.5 * speedup,
+                },
+                BossPhase {
+                    health_threshold: 0.66,
+                    movement: BehaviourState::charge(),
+                    attack: BossAttack::SummonMinions { count: 4 },
+                    attack_interval: 6.0 * speedup,
+                },
+                BossPhase {
+                    health_threshold: 0.33,
+                    movement: BehaviourState::keep_distance(350.0),
+                    attack: BossAttack::BulletRing { count: 24 },
+                    attack_interval: 1.2 * speedup,
+                },
+            ],
+            phase_index: 0,
+            time_since_attack: 0.0,
+        }
+    }
+
+    pub fn get_phase(&self) -> &BossPhase {
+        &self.phases[self.phase_index]
+    }
+
+    /// Advances the phase when health crosses a threshold, swapping the
+    /// boss's movement over, and returns the attack to make this frame.
+    pub fn update(
+        &mut self,
+        behaviour: &mut Behaviour,
+        health_fraction: f32,
+        delta: f32,
+    ) -> Option<BossAttack> {
+        let mut changed_phase = false;
+        while self
+            .phases
+            .get(self.phase_index + 1)
+            .is_some_and(|next| health_fraction < next.health_threshold)
+        {
+            self.phase_index += 1;
+            changed_phase = true;
+        }
+        if changed_phase {
+            *behaviour = Behaviour::new(self.get_phase().movement);
+            self.time_since_attack = 0.0;
+        }
+
+        self.time_since_attack += delta;
+        let phase = *self.get_phase();
+        if self.time_since_attack >= phase.attack_interval {
+            self.time_since_attack = 0.0;
+            return Some(phase.attack);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boss_attacks_on_interval() {
+        let mut boss = Boss::new(0);
+        let mut behaviour = Behaviour::new(boss.get_phase().movement);
+        let interval = boss.get_phase().attack_interval;
+
+        assert_eq!(boss.update(&mut behaviour, 1.0, interval * 0.5), None);
+        assert_eq!(
+            boss.update(&mut behaviour, 1.0, interval * 0.5),
+            Some(BossAttack::BulletRing { count: 12 })
+        );
+    }
+
+    #[test]
+    fn test_boss_changes_phase_at_health_thresholds() {
+        let mut boss = Boss::new(0);
+        let mut behaviour = Behaviour::new(boss.get_phase().movement);
+
+        boss.update(&mut behaviour, 0.5, 0.0);
+        assert_eq!(boss.phase_index, 1);
+        assert_eq!(behaviour.primary, BehaviourState::charge());
+
+        // A big hit can skip straight past a phase
+        let mut boss = Boss::new(0);
+        boss.update(&mut behaviour, 0.1, 0.0);
+        assert_eq!(boss.phase_index, 2);
+        assert_eq!(behaviour.primary, BehaviourState::keep_distance(350.0));
+
+        // Healing never rolls the phase back
+        boss.update(&mut behaviour, 1.0, 0.0);
+        assert_eq!(boss.phase_index, 2);
+    }
+}
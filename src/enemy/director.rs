@@ -0,0 +1,751 @@
+use std::{collections::HashMap, str::FromStr};
+
+use rand::prelude::*;
+
+use crate::{
+    enemy::{
+        affixes,
+        behaviour::{Behaviour, BehaviourState},
+        Enemy, EnemyType,
+    },
+    utils::Position,
+};
+
+pub const DIRECTOR_SCRIPT_PATH: &str = "./assests/data/spawn_director.txt";
+
+// Viewport and spawn positioning
+const SCREEN_HALF_WIDTH: f32 = 1240.0; // 2480 / 2
+const SCREEN_HALF_HEIGHT: f32 = 720.0; // 1440 / 2
+const SPAWN_BUFFER: f32 = 100.0; // Pixels outside viewport to spawn
+
+// Enemies this far past the edge of the screen still count towards the target
+const ON_SCREEN_MARGIN: f32 = SPAWN_BUFFER * 2.0;
+const AMBUSH_SPREAD: f32 = 150.0;
+const CLUSTER_SIZE: usize = 5;
+const CLUSTER_SPREAD: f32 = 40.0;
+
+// Event names stay on screen this long
+const ANNOUNCEMENT_TIME: f32 = 3.0;
+
+#[derive(Clone, Copy)]
+enum SpawnEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl SpawnEdge {
+    fn random(rng: &mut impl Rng) -> Self {
+        match rng.random_range(0..4) {
+            0 => SpawnEdge::Top,
+            1 => SpawnEdge::Bottom,
+            2 => SpawnEdge::Left,
+            _ => SpawnEdge::Right,
+        }
+    }
+
+    /// Direction pointing from this edge across the screen.
+    fn inward_angle(&self) -> f32 {
+        match self {
+            SpawnEdge::Top => std::f32::consts::FRAC_PI_2,
+            SpawnEdge::Bottom => -std::f32::consts::FRAC_PI_2,
+            SpawnEdge::Left => 0.0,
+            SpawnEdge::Right => std::f32::consts::PI,
+        }
+    }
+
+    /// Point just off this edge of the screen, `t` (0.0 to 1.0) of the way along it.
+    fn point_along(&self, player_pos: &Position, t: f32) -> Position {
+        // Calculate viewport bounds in world space
+        let view_left = player_pos.x - SCREEN_HALF_WIDTH;
+        let view_top = player_pos.y - SCREEN_HALF_HEIGHT;
+        let across = view_left + SCREEN_HALF_WIDTH * 2.0 * t;
+        let down = view_top + SCREEN_HALF_HEIGHT * 2.0 * t;
+
+        let (x, y) = match self {
+            SpawnEdge::Top => (across, view_top - SPAWN_BUFFER),
+            SpawnEdge::Bottom => (across, player_pos.y + SCREEN_HALF_HEIGHT + SPAWN_BUFFER),
+            SpawnEdge::Left => (view_left - SPAWN_BUFFER, down),
+            SpawnEdge::Right => (player_pos.x + SCREEN_HALF_WIDTH + SPAWN_BUFFER, down),
+        };
+        Position { x, y }
+    }
+}
+
+fn calculate_spawn_position(player_pos: &Position, rng: &mut impl Rng) -> Position {
+    SpawnEdge::random(rng).point_along(player_pos, rng.random())
+}
+
+/// Whether an enemy is close enough to the screen to count as on it.
+fn is_on_screen(player_pos: &Position, position: &Position) -> bool {
+    (position.x - player_pos.x).abs() <= SCREEN_HALF_WIDTH + ON_SCREEN_MARGIN
+        && (position.y - player_pos.y).abs() <= SCREEN_HALF_HEIGHT + ON_SCREEN_MARGIN
+}
+
+/// An enemy the script can ask for by name.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SpawnKind {
+    ServoSkull,
+    /// Servo skull that flanks instead of flying straight in.
+    SwarmSkull,
+    DarkFighter,
+    Exploder,
+    Tank,
+    Splitter,
+    Boss,
+}
+
+impl FromStr for SpawnKind {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "servo_skull" => Ok(SpawnKind::ServoSkull),
+            "swarm_skull" => Ok(SpawnKind::SwarmSkull),
+            "dark_fighter" => Ok(SpawnKind::DarkFighter),
+            "exploder" => Ok(SpawnKind::Exploder),
+            "tank" => Ok(SpawnKind::Tank),
+            "splitter" => Ok(SpawnKind::Splitter),
+            "boss" => Ok(SpawnKind::Boss),
+            _ => Err(format!("unknown enemy `{}`", name)),
+        }
+    }
+}
+
+/// Where a group of spawns is placed around the player.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Formation {
+    /// Each enemy on a random screen edge.
+    Scattered,
+    /// Evenly spaced all the way around the player, just off screen, then
+    /// closing in together.
+    Ring,
+    /// A wall along one screen edge that sweeps straight across the screen.
+    Line,
+    /// Bunched up at one point on a screen edge.
+    Ambush,
+    /// Several tight packs of `CLUSTER_SIZE` on random edges.
+    Cluster,
+}
+
+impl FromStr for Formation {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "scattered" => Ok(Formation::Scattered),
+            "ring" => Ok(Formation::Ring),
+            "line" => Ok(Formation::Line),
+            "ambush" => Ok(Formation::Ambush),
+            "cluster" => Ok(Formation::Cluster),
+            _ => Err(format!("unknown formation `{}`", name)),
+        }
+    }
+}
+
+/// Where one enemy of a formation starts, and how it should move until the
+/// formation breaks up.
+pub struct Placement {
+    pub position: Position,
+    pub movement: Option<BehaviourState>,
+}
+
+impl Formation {
+    pub fn place(&self, player_pos: &Position, count: usize, rng: &mut impl Rng) -> Vec<Placement> {
+        match self {
+            Formation::Scattered => (0..count)
+                .map(|_| Placement {
+                    position: calculate_spawn_position(player_pos, rng),
+                    movement: None,
+                })
+                .collect(),
+            Formation::Ring => {
+                // Corner of the screen plus the buffer, so the whole ring starts off screen
+                let radius = (SCREEN_HALF_WIDTH * SCREEN_HALF_WIDTH
+                    + SCREEN_HALF_HEIGHT * SCREEN_HALF_HEIGHT)
+                    .sqrt()
+                    + SPAWN_BUFFER;
+                let start_angle = rng.random_range(0.0..std::f32::consts::TAU);
+                (0..count)
+                    .map(|index| {
+                        let angle =
+                            start_angle + std::f32::consts::TAU * index as f32 / count as f32;
+                        Placement {
+                            position: Position {
+                                x: player_pos.x + angle.cos() * radius,
+                                y: player_pos.y + angle.sin() * radius,
+                            },
+                            movement: Some(BehaviourState::Encircle { angle, radius }),
+                        }
+                    })
+                    .collect()
+            }
+            Formation::Line => {
+                let edge = SpawnEdge::random(rng);
+                (0..count)
+                    .map(|index| Placement {
+                        position: edge.point_along(player_pos, (index as f32 + 0.5) / count as f32),
+                        movement: Some(BehaviourState::sweep(edge.inward_angle())),
+                    })
+                    .collect()
+            }
+            Formation::Ambush => {
+                let center = calculate_spawn_position(player_pos, rng);
+                (0..count)
+                    .map(|_| Placement {
+                        position: Position {
+                            x: center.x + rng.random_range(-AMBUSH_SPREAD..AMBUSH_SPREAD),
+                            y: center.y + rng.random_range(-AMBUSH_SPREAD..AMBUSH_SPREAD),
+                        },
+                        movement: None,
+                    })
+                    .collect()
+            }
+            Formation::Cluster => {
+                let mut placements = vec![];
+                while placements.len() < count {
+                    let center = calculate_spawn_position(player_pos, rng);
+                    let pack_size = CLUSTER_SIZE.min(count - placements.len());
+                    for _ in 0..pack_size {
+                        placements.push(Placement {
+                            position: Position {
+                                x: center.x + rng.random_range(-CLUSTER_SPREAD..CLUSTER_SPREAD),
+                                y: center.y + rng.random_range(-CLUSTER_SPREAD..CLUSTER_SPREAD),
+                            },
+                            movement: None,
+                        });
+                    }
+                }
+                placements
+            }
+        }
+    }
+}
+
+/// A stretch of the run with its own enemy mix and on-screen target.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SpawnWindow {
+    pub start: f32,
+    pub end: f32,
+    /// On-screen enemy count to top up to, ramping from the first to the
+    /// second value across the window.
+    pub target: (usize, usize),
+    pub spawn_interval: f32,
+    pub max_per_spawn: usize,
+    /// Enemy kinds with their relative weights.
+    pub mix: Vec<(SpawnKind, u32)>,
+    pub formation: Formation,
+}
+
+impl SpawnWindow {
+    fn contains(&self, elapsed_time: f32) -> bool {
+        elapsed_time >= self.start && elapsed_time < self.end
+    }
+
+    pub fn get_target_count(&self, elapsed_time: f32) -> usize {
+        let (from, to) = self.target;
+        if !self.end.is_finite() || from == to {
+            return from;
+        }
+        let progress = ((elapsed_time - self.start) / (self.end - self.start)).clamp(0.0, 1.0);
+        (from as f32 + (to as f32 - from as f32) * progress).round() as usize
+    }
+
+    fn pick_kind(&self, rng: &mut impl Rng) -> SpawnKind {
+        self.mix
+            .choose_weighted(rng, |(_, weight)| *weight)
+            .map(|(kind, _)| *kind)
+            .unwrap_or(SpawnKind::ServoSkull)
+    }
+}
+
+/// A one-off group that arrives at a set time, like a boss or a swarm.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SpawnEvent {
+    pub time: f32,
+    /// Shown on screen when the event fires.
+    pub name: Option<String>,
+    pub kind: SpawnKind,
+    pub count: usize,
+    pub formation: Formation,
+}
+
+/// Spawn windows and events loaded from the director script.
+///
+/// The script is made of `[window]` and `[event]` sections holding
+/// `key = value` lines; `#` starts a comment. Times are seconds or `m:ss`.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct DirectorScript {
+    pub windows: Vec<SpawnWindow>,
+    /// Sorted by time.
+    pub events: Vec<SpawnEvent>,
+}
+
+impl DirectorScript {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| format!("unable to read {}: {}", path, error))?;
+        Self::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut script = DirectorScript::default();
+        let mut section: Option<Section> = None;
+
+        for (index, raw_line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = raw_line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = line
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+            {
+                if let Some(finished) = section.take() {
+                    script.add_section(finished)?;
+                }
+                section = Some(Section {
+                    name: name.trim().to_string(),
+                    line_number,
+                    values: HashMap::new(),
+                });
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(format!("line {}: expected `key = value`", line_number));
+            };
+            let Some(current) = section.as_mut() else {
+                return Err(format!(
+                    "line {}: `{}` must be inside a [window] or [event]",
+                    line_number,
+                    key.trim()
+                ));
+            };
+            current.values.insert(
+                key.trim().to_string(),
+                (line_number, value.trim().to_string()),
+            );
+        }
+        if let Some(finished) = section.take() {
+            script.add_section(finished)?;
+        }
+
+        script.events.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Ok(script)
+    }
+
+    fn add_section(&mut self, section: Section) -> Result<(), String> {
+        match section.name.as_str() {
+            "window" => {
+                section.check_keys(&[
+                    "start",
+                    "end",
+                    "target",
+                    "spawn_interval",
+                    "max_per_spawn",
+                    "mix",
+                    "formation",
+                ])?;
+                let target = match section.get("target") {
+                    Some((line_number, value)) => parse_target(value)
+                        .map_err(|error| format!("line {}: {}", line_number, error))?,
+                    None => return Err(section.missing("target")),
+                };
+                let mix = match section.get("mix") {
+                    Some((line_number, value)) => parse_mix(value)
+                        .map_err(|error| format!("line {}: {}", line_number, error))?,
+                    None => return Err(section.missing("mix")),
+                };
+                self.windows.push(SpawnWindow {
+                    start: section.time("start")?.unwrap_or(0.0),
+                    end: section.time("end")?.unwrap_or(f32::INFINITY),
+                    target,
+                    spawn_interval: section.value("spawn_interval")?.unwrap_or(1.0),
+                    max_per_spawn: section.value("max_per_spawn")?.unwrap_or(3),
+                    mix,
+                    formation: section.value("formation")?.unwrap_or(Formation::Scattered),
+                });
+            }
+            "event" => {
+                section.check_keys(&["at", "name", "enemy", "count", "formation"])?;
+                self.events.push(SpawnEvent {
+                    time: section.time("at")?.ok_or_else(|| section.missing("at"))?,
+                    name: section.get("name").map(|(_, name)| name.to_string()),
+                    kind: section
+                        .value("enemy")?
+                        .ok_or_else(|| section.missing("enemy"))?,
+                    count: section.value("count")?.unwrap_or(1),
+                    formation: section.value("formation")?.unwrap_or(Formation::Scattered),
+                });
+            }
+            other => {
+                return Err(format!(
+                    "line {}: unknown section [{}]",
+                    section.line_number, other
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One `[window]` or `[event]` block while it is being read.
+struct Section {
+    name: String,
+    line_number: usize,
+    /// Key to (line number, raw value).
+    values: HashMap<String, (usize, String)>,
+}
+
+impl Section {
+    fn get(&self, key: &str) -> Option<(usize, &str)> {
+        self.values
+            .get(key)
+            .map(|(line_number, value)| (*line_number, value.as_str()))
+    }
+
+    fn value<T: FromStr>(&self, key: &str) -> Result<Option<T>, String>
+    where
+        T::Err: ToString,
+    {
+        let Some((line_number, value)) = self.get(key) else {
+            return Ok(None);
+        };
+        value.parse().map(Some).map_err(|error: T::Err| {
+            format!(
+                "line {}: bad {} `{}`: {}",
+                line_number,
+                key,
+                value,
+                error.to_string()
+            )
+        })
+    }
+
+    fn time(&self, key: &str) -> Result<Option<f32>, String> {
+        let Some((line_number, value)) = self.get(key) else {
+            return Ok(None);
+        };
+        parse_time(value)
+            .map(Some)
+            .ok_or_else(|| format!("line {}: bad time `{}`", line_number, value))
+    }
+
+    fn check_keys(&self, allowed: &[&str]) -> Result<(), String> {
+        for (key, (line_number, _)) in self.values.iter() {
+            if !allowed.contains(&key.as_str()) {
+                return Err(format!(
+                    "line {}: unknown key `{}` in [{}]",
+                    line_number, key, self.name
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn missing(&self, key: &str) -> String {
+        format!(
+            "line {}: [{}] is missing `{}`",
+            self.line_number, self.name, key
+        )
+    }
+}
+
+/// Seconds, or minutes and seconds as `m:ss`.
+fn parse_time(value: &str) -> Option<f32> {
+    match value.split_once(':') {
+        Some((minutes, seconds)) => {
+            Some(minutes.trim().parse::<f32>().ok()? * 60.0 + seconds.trim().parse::<f32>().ok()?)
+        }
+        None => value.parse().ok(),
+    }
+}
+
+/// A single count, or `from..to` to ramp across the window.
+fn parse_target(value: &str) -> Result<(usize, usize), String> {
+    let parse = |count: &str| {
+        count
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| format!("bad target `{}`", value))
+    };
+    match value.split_once("..") {
+        Some((from, to)) => Ok((parse(from)?, parse(to)?)),
+        None => {
+            let count = parse(value)?;
+            Ok((count, count))
+        }
+    }
+}
+
+/// Comma separated `enemy weight` pairs; the weight defaults to 1.
+fn parse_mix(value: &str) -> Result<Vec<(SpawnKind, u32)>, String> {
+    let mut mix = vec![];
+    for entry in value.split(',') {
+        let mut parts = entry.split_whitespace();
+        let Some(name) = parts.next() else {
+            return Err("empty entry in mix".to_string());
+        };
+        let weight = match parts.next() {
+            Some(weight) => weight
+                .parse()
+                .map_err(|_| format!("bad weight `{}` for {}", weight, name))?,
+            None => 1,
+        };
+        mix.push((name.parse()?, weight));
+    }
+    Ok(mix)
+}
+
+/// A scripted event name being shown on screen.
+pub struct Announcement {
+    pub text: String,
+    pub remaining: f32,
+}
+
+/// Runs the director script: fires events as their time comes round and
+/// keeps the on-screen enemy count topped up to the active window's target.
+pub struct SpawnDirector {
+    pub script: DirectorScript,
+    next_event: usize,
+    time_since_spawn: f32,
+    bosses_spawned: usize,
+    pub announcement: Option<Announcement>,
+}
+
+impl SpawnDirector {
+    pub fn new(script: DirectorScript) -> Self {
+        SpawnDirector {
+            script,
+            next_event: 0,
+            time_since_spawn: 0.0,
+            bosses_spawned: 0,
+            announcement: None,
+        }
+    }
+
+    pub fn get_active_window(&self, elapsed_time: f32) -> Option<&SpawnWindow> {
+        self.script
+            .windows
+            .iter()
+            .find(|window| window.contains(elapsed_time))
+    }
+
+    /// Returns the enemies to add this frame.
+    pub fn update(
+        &mut self,
+        delta: f32,
+        elapsed_time: f32,
+        enemies: &[Enemy],
+        player_pos: &Position,
+        rng: &mut impl Rng,
+    ) -> Vec<Enemy> {
+        if let Some(announcement) = self.announcement.as_mut() {
+            announcement.remaining -= delta;
+            if announcement.remaining <= 0.0 {
+                self.announcement = None;
+            }
+        }
+
+        let mut spawned = vec![];
+
+        // Events fire once each, in order
+        while let Some(event) = self.script.events.get(self.next_event).cloned() {
+            if elapsed_time < event.time {
+                break;
+            }
+            for placement in event.formation.place(player_pos, event.count, rng) {
+                spawned.push(self.spawn(event.kind, placement, elapsed_time, rng));
+            }
+            if let Some(text) = event.name {
+                self.announcement = Some(Announcement {
+                    text,
+                    remaining: ANNOUNCEMENT_TIME,
+                });
+            }
+            self.next_event += 1;
+        }
+
+        self.time_since_spawn += delta;
+        let Some(window) = self.get_active_window(elapsed_time).cloned() else {
+            return spawned;
+        };
+        if self.time_since_spawn < window.spawn_interval {
+            return spawned;
+        }
+        self.time_since_spawn = 0.0;
+
+        let on_screen = enemies
+            .iter()
+            .filter(|enemy| is_on_screen(player_pos, &enemy.position))
+            .count()
+            + spawned.len();
+        let missing = window
+            .get_target_count(elapsed_time)
+            .saturating_sub(on_screen)
+            .min(window.max_per_spawn);
+        for placement in window.formation.place(player_pos, missing, rng) {
+            let kind = window.pick_kind(rng);
+            spawned.push(self.spawn(kind, placement, elapsed_time, rng));
+        }
+        spawned
+    }
+
+    fn spawn(
+        &mut self,
+        kind: SpawnKind,
+        placement: Placement,
+        elapsed_time: f32,
+        rng: &mut impl Rng,
+    ) -> Enemy {
+        let position = placement.position;
+        let mut enemy = match kind {
+            SpawnKind::ServoSkull => EnemyType::new_servo_skull(position),
+            SpawnKind::SwarmSkull => {
+                let mut servo_skull = EnemyType::new_servo_skull(position);
+                servo_skull.behaviour = Behaviour::new(BehaviourState::swarm(rng));
+                servo_skull
+            }
+            SpawnKind::DarkFighter => EnemyType::new_dark_fighter(position),
+            SpawnKind::Exploder => EnemyType::new_exploder(position),
+            SpawnKind::Tank => EnemyType::new_tank(position),
+            SpawnKind::Splitter => EnemyType::new_splitter(position, 0),
+            SpawnKind::Boss => {
+                // Bosses are already their own reward, no affixes on top
+                let boss = EnemyType::new_boss(position, self.bosses_spawned);
+                self.bosses_spawned += 1;
+                return boss;
+            }
+        };
+        // The formation steers until it breaks up, then the enemy's own behaviour takes over
+        if let Some(movement) = placement.movement {
+            enemy.behaviour.state = movement;
+        }
+        affixes::roll_elite(&mut enemy, elapsed_time, rng);
+        enemy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SCRIPT: &str = "
+        # Opening
+        [window]
+        start = 0
+        end = 1:00
+        target = 10..20
+        spawn_interval = 0.5
+        max_per_spawn = 4
+        mix = servo_skull 3, swarm_skull
+
+        [event]
+        at = 0:30
+        name = Swarm
+        enemy = swarm_skull
+        count = 12
+        formation = ring
+    ";
+
+    #[test]
+    fn test_parse_director_script() {
+        let script = DirectorScript::parse(TEST_SCRIPT).unwrap();
+
+        assert_eq!(script.windows.len(), 1);
+        let window = &script.windows[0];
+        assert_eq!(window.end, 60.0);
+        assert_eq!(window.target, (10, 20));
+        assert_eq!(
+            window.mix,
+            vec![(SpawnKind::ServoSkull, 3), (SpawnKind::SwarmSkull, 1)]
+        );
+        assert_eq!(window.formation, Formation::Scattered);
+        assert_eq!(window.get_target_count(30.0), 15);
+
+        assert_eq!(script.events.len(), 1);
+        assert_eq!(script.events[0].time, 30.0);
+        assert_eq!(script.events[0].formation, Formation::Ring);
+    }
+
+    #[test]
+    fn test_formations_place_every_enemy() {
+        let player_pos = Position { x: 0.0, y: 0.0 };
+        let mut rng = rand::rng();
+
+        let ring = Formation::Ring.place(&player_pos, 8, &mut rng);
+        assert_eq!(ring.len(), 8);
+        assert!(ring
+            .iter()
+            .all(|placement| matches!(placement.movement, Some(BehaviourState::Encircle { .. }))));
+
+        // Every enemy in the wall sweeps the same way
+        let wall = Formation::Line.place(&player_pos, 6, &mut rng);
+        let first = wall[0].movement;
+        assert!(matches!(first, Some(BehaviourState::Sweep { .. })));
+        assert!(wall.iter().all(|placement| placement.movement == first));
+
+        assert_eq!(
+            Formation::Cluster.place(&player_pos, 12, &mut rng).len(),
+            12
+        );
+    }
+
+    #[test]
+    fn test_shipped_script_loads() {
+        let script = DirectorScript::load(DIRECTOR_SCRIPT_PATH).unwrap();
+        assert!(!script.windows.is_empty());
+        assert!(script
+            .events
+            .iter()
+            .any(|event| event.kind == SpawnKind::Boss));
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_the_line() {
+        let error = DirectorScript::parse("[window]\ntarget = 5\nmix = grot").unwrap_err();
+        assert!(error.starts_with("line 3"), "{}", error);
+
+        let error = DirectorScript::parse("[event]\nat = 10\nenemy = tank\nspeed = 2").unwrap_err();
+        assert!(error.contains("unknown key `speed`"), "{}", error);
+    }
+
+    #[test]
+    fn test_director_tops_up_to_target() {
+        let script = DirectorScript::parse(TEST_SCRIPT).unwrap();
+        let mut director = SpawnDirector::new(script);
+        let player_pos = Position { x: 0.0, y: 0.0 };
+        let mut rng = rand::rng();
+
+        let mut enemies = vec![];
+        for _ in 0..10 {
+            let mut spawned = director.update(0.5, 0.0, &enemies, &player_pos, &mut rng);
+            assert!(spawned.len() <= 4, "Never more than max_per_spawn");
+            enemies.append(&mut spawned);
+        }
+        assert_eq!(enemies.len(), 10, "Stops once the target is reached");
+    }
+
+    #[test]
+    fn test_events_fire_once() {
+        let script = DirectorScript::parse(TEST_SCRIPT).unwrap();
+        let mut director = SpawnDirector::new(script);
+        let player_pos = Position { x: 0.0, y: 0.0 };
+        let mut rng = rand::rng();
+        // Plenty of enemies already around so only the event spawns
+        let crowd: Vec<Enemy> = (0..20)
+            .map(|_| EnemyType::new_servo_skull(player_pos))
+            .collect();
+
+        let spawned = director.update(0.1, 30.0, &crowd, &player_pos, &mut rng);
+        assert_eq!(spawned.len(), 12);
+        assert_eq!(director.announcement.as_ref().unwrap().text, "Swarm");
+
+        let spawned = director.update(0.1, 30.1, &crowd, &player_pos, &mut rng);
+        assert!(spawned.is_empty());
+    }
+}
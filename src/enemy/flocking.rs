@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use crate::{enemy::behaviour::BehaviourState, enemy::Enemy, utils::Position};
+
+// Separation keeps enemies from stacking on top of each other
+const SEPARATION_STRENGTH: f32 = 3000.0;
+const SEPARATION_SPACING: f32 = 0.8; // Fraction of the sprite size enemies may overlap to
+
+// Alignment and cohesion only apply between swarming enemies
+const FLOCK_RADIUS: f32 = 200.0;
+const ALIGNMENT_WEIGHT: f32 = 2.0;
+const COHESION_WEIGHT: f32 = 1.5;
+
+/// Buckets enemy indices into square cells so neighbour lookups only check
+/// nearby enemies instead of the whole horde.
+pub struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl SpatialGrid {
+    pub fn new(cell_size: f32) -> Self {
+        SpatialGrid {
+            cell_size,
+            cells: HashMap::new(),
+        }
+    }
+
+    fn cell_of(&self, position: &Position) -> (i32, i32) {
+        (
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    pub fn insert(&mut self, index: usize, position: &Position) {
+        let cell = self.cell_of(position);
+        self.cells.entry(cell).or_default().push(index);
+    }
+
+    /// Indices in every cell touching the square of `radius` around `position`.
+    /// Callers still need to check the exact distance.
+    pub fn query(&self, position: &Position, radius: f32) -> Vec<usize> {
+        let (min_x, min_y) = self.cell_of(&Position {
+            x: position.x - radius,
+            y: position.y - radius,
+        });
+        let (max_x, max_y) = self.cell_of(&Position {
+            x: position.x + radius,
+            y: position.y + radius,
+        });
+
+        let mut found = vec![];
+        for cell_x in min_x..=max_x {
+            for cell_y in min_y..=max_y {
+                if let Some(indices) = self.cells.get(&(cell_x, cell_y)) {
+                    found.extend_from_slice(indices);
+                }
+            }
+        }
+        found
+    }
+}
+
+/// Extra acceleration for each enemy from its neighbours: separation for
+/// everyone, plus alignment and cohesion between swarmers.
+/// `radii` holds each enemy's half sprite size.
+pub fn calculate_flocking(enemies: &[Enemy], radii: &[f32]) -> Vec<(f32, f32)> {
+    let largest_radius = radii.iter().copied().fold(0.0, f32::max);
+    let cell_size = (largest_radius * 2.0).max(FLOCK_RADIUS);
+    let mut grid = SpatialGrid::new(cell_size);
+    for (index, enemy) in enemies.iter().enumerate() {
+        grid.insert(index, &enemy.position);
+    }
+
+    enemies
+        .iter()
+        .enumerate()
+        .map(|(index, enemy)| {
+            let is_swarming = matches!(enemy.behaviour.state, BehaviourState::Swarm { .. });
+            let mut force_x = 0.0;
+            let mut force_y = 0.0;
+
+            let mut flock_count = 0;
+            let mut flock_velocity = (0.0, 0.0);
+            let mut flock_center = (0.0, 0.0);
+
+            for other_index in grid.query(&enemy.position, cell_size) {
+                if other_index == index {
+                    continue;
+                }
+                let other = &enemies[other_index];
+                let dx = enemy.position.x - other.position.x;
+                let dy = enemy.position.y - other.position.y;
+                let distance = (dx * dx + dy * dy).sqrt();
+
+                let spacing = (radii[index] + radii[other_index]) * SEPARATION_SPACING;
+                if distance < spacing {
+                    // Push harder the more they overlap; pick an axis if exactly stacked
+                    let push = SEPARATION_STRENGTH * (1.0 - distance / spacing);
+                    if distance > 0.0 {
+                        force_x += dx / distance * push;
+                        force_y += dy / distance * push;
+                    } else if index < other_index {
+                        force_x -= push;
+                    } else {
+                        force_x += push;
+                    }
+                }
+
+                let other_swarming = matches!(other.behaviour.state, BehaviourState::Swarm { .. });
+                if is_swarming && other_swarming && distance < FLOCK_RADIUS {
+                    flock_count += 1;
+                    flock_velocity.0 += other.velocity_x;
+                    flock_velocity.1 += other.velocity_y;
+                    flock_center.0 += other.position.x;
+                    flock_center.1 += other.position.y;
+                }
+            }
+
+            if flock_count > 0 {
+                let count = flock_count as f32;
+                force_x += (flock_velocity.0 / count - enemy.velocity_x) * ALIGNMENT_WEIGHT;
+                force_y += (flock_velocity.1 / count - enemy.velocity_y) * ALIGNMENT_WEIGHT;
+                force_x += (flock_center.0 / count - enemy.position.x) * COHESION_WEIGHT;
+                force_y += (flock_center.1 / count - enemy.position.y) * COHESION_WEIGHT;
+            }
+
+            (force_x, force_y)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemy::EnemyType;
+
+    #[test]
+    fn test_spatial_grid_only_returns_nearby_cells() {
+        let mut grid = SpatialGrid::new(100.0);
+        grid.insert(0, &Position { x: 10.0, y: 10.0 });
+        grid.insert(1, &Position { x: 150.0, y: 10.0 });
+        grid.insert(
+            2,
+            &Position {
+                x: 1000.0,
+                y: 1000.0,
+            },
+        );
+
+        let found = grid.query(&Position { x: 50.0, y: 50.0 }, 100.0);
+
+        assert!(found.contains(&0));
+        assert!(found.contains(&1));
+        assert!(!found.contains(&2));
+    }
+
+    #[test]
+    fn test_overlapping_enemies_are_pushed_apart() {
+        let enemies = vec![
+            EnemyType::new_servo_skull(Position { x: 0.0, y: 0.0 }),
+            EnemyType::new_servo_skull(Position { x: 10.0, y: 0.0 }),
+            EnemyType::new_servo_skull(Position { x: 500.0, y: 0.0 }),
+        ];
+        let radii = vec![32.0; enemies.len()];
+
+        let forces = calculate_flocking(&enemies, &radii);
+
+        assert!(forces[0].0 < 0.0, "Left enemy should be pushed left");
+        assert!(forces[1].0 > 0.0, "Right enemy should be pushed right");
+        assert_eq!(forces[2], (0.0, 0.0), "Far away enemy is left alone");
+    }
+}
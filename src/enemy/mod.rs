@@ -0,0 +1,1286 @@
+use std::collections::HashMap;
+
+pub mod affixes;
+pub mod behaviour;
+pub mod boss;
+pub mod director;
+pub mod flocking;
+
+use rand::prelude::*;
+use raylib::prelude::*;
+
+use affixes::Elite;
+use behaviour::{Behaviour, BehaviourState};
+use boss::{Boss, BossAttack};
+use director::{DirectorScript, SpawnDirector};
+
+use crate::{
+    drops::{DropKind, DroppedItem},
+    player::Player,
+    projectiles::Projectile,
+    statuses::{self, Status},
+    utils::{Direction, Position},
+};
+
+const SPEED: f32 = 2000.0;
+const BRAKE_RATE: f32 = 8.0; // How quickly enemies stop when their behaviour wants to stand still
+
+// Tanks lumber about until the player comes this close
+const TANK_AGGRO_RANGE: f32 = 900.0;
+
+// Splitters break into 2-3 copies that are each smaller, weaker and faster
+const SPLITTER_MAX_GENERATION: u32 = 2;
+const SPLIT_COUNT_MIN: u32 = 2;
+const SPLIT_COUNT_MAX: u32 = 3;
+const SPLIT_SCALE: f32 = 0.7;
+const SPLIT_SPEED_FACTOR: f32 = 1.3;
+const SPLIT_SPREAD: f32 = 30.0; // How far from the parent the children appear
+
+// Bosses are spawned by the director script
+const BOSS_SCALE: f32 = 2.5;
+const BOSS_BASE_HEALTH: i32 = 1500;
+const BOSS_SUMMON_RADIUS: f32 = 150.0;
+
+// Hard cap on enemy velocity per axis, only dashes go past it. Enemies slower
+// than the base `speed` top out proportionally lower.
+const MAX_VELOCITY: f32 = 250.0;
+const BASE_ENEMY_SPEED: f32 = 450.0;
+// Armor never soaks a hit completely
+const MIN_ARMORED_DAMAGE: i32 = 1;
+
+// How long an explosion stays on screen
+pub const EXPLOSION_FADE_TIME: f32 = 0.3;
+// Shots only start winding up once the player is this close to the preferred distance
+const RANGED_ENGAGE_FACTOR: f32 = 1.5;
+// Revive shockwave
+const SHOCKWAVE_SPEED: f32 = 1000.0;
+const SHOCKWAVE_KNOCKBACK_TIME: f32 = 0.4;
+// Gap between drops left by the same enemy
+const DROP_SPREAD: f32 = 48.0;
+
+pub struct EnemyTextures<'a> {
+    pub servo_skull: &'a Texture2D,
+    pub dark_fighter: &'a Texture2D,
+    pub dark_tanker: &'a Texture2D,
+    pub crescent_moon: &'a Texture2D,
+}
+
+/// A blast that already dealt its damage, kept around so it can be drawn.
+pub struct Explosion {
+    pub position: Position,
+    pub radius: f32,
+    pub age: f32,
+}
+
+pub struct AllEnemies<'a> {
+    pub enemies: Vec<Enemy>,
+    pub explosions: Vec<Explosion>,
+    pub director: SpawnDirector,
+    pub texture_map: HashMap<EnemyType, &'a Texture2D>,
+}
+
+impl<'a> AllEnemies<'a> {
+    pub fn new(textures: EnemyTextures<'a>, director_script: DirectorScript) -> Self {
+        let mut texture_map = HashMap::new();
+        texture_map.insert(EnemyType::servo_skull_type(), textures.servo_skull);
+        texture_map.insert(EnemyType::DarkFighter, textures.dark_fighter);
+        texture_map.insert(EnemyType::Tank, textures.dark_tanker);
+        texture_map.insert(EnemyType::Splitter, textures.crescent_moon);
+        // Bosses are oversized tankers
+        texture_map.insert(EnemyType::Boss, textures.dark_tanker);
+        // Exploders are tinted servo skulls
+        texture_map.insert(EnemyType::Exploder, textures.servo_skull);
+
+        Self {
+            enemies: vec![],
+            explosions: vec![],
+            director: SpawnDirector::new(director_script),
+            texture_map,
+        }
+    }
+
+    /// Returns the projectiles fired by ranged enemies this frame.
+    pub fn tick(&mut self, player: &mut Player, delta: &f32) -> Vec<Projectile> {
+        for explosion in self.explosions.iter_mut() {
+            explosion.age += delta;
+        }
+        self.explosions
+            .retain(|explosion| explosion.age < EXPLOSION_FADE_TIME);
+
+        let radii: Vec<f32> = self
+            .enemies
+            .iter()
+            .map(|enemy| {
+                let texture = self
+                    .texture_map
+                    .get(&enemy.enemy_type)
+                    .expect("unable to find texture");
+                texture.width as f32 * enemy.scale / 2.0
+            })
+            .collect();
+        let flocking = flocking::calculate_flocking(&self.enemies, &radii);
+
+        let mut shots = vec![];
+        let mut summoned = vec![];
+        for (mut enemy, (flock_x, flock_y)) in self.enemies.iter_mut().zip(flocking) {
+            enemy.flock_x = flock_x;
+            enemy.flock_y = flock_y;
+            enemy.handle_status_effects(delta);
+            affixes::handle_elite(player, enemy, delta);
+            handle_movement(player, &mut enemy, delta);
+            handle_player_collision(&self.texture_map, player, &mut enemy, delta);
+            handle_ranged_attack(player, enemy, delta, &mut shots);
+            handle_boss_attack(enemy, delta, &mut shots, &mut summoned);
+
+            // A finished fuse kills the exploder, its death effect does the rest
+            if enemy.behaviour.has_detonated() {
+                enemy.health = 0;
+            }
+        }
+        self.enemies.append(&mut summoned);
+        shots
+    }
+
+    /// The boss currently on the field, if any.
+    pub fn get_boss(&self) -> Option<&Enemy> {
+        self.enemies.iter().find(|enemy| enemy.boss.is_some())
+    }
+
+    /// Removes dead enemies, running their death effects first. Explosions
+    /// can kill other enemies, so this keeps going until nothing else dies.
+    /// Anything spawned by a death joins the horde once the chain is over, so
+    /// it can't be caught in the blast that killed its parent.
+    /// Returns whatever the dead left on the ground.
+    pub fn handle_deaths(&mut self, player: &mut Player) -> Vec<DroppedItem> {
+        let mut spawned = vec![];
+        let mut drops = vec![];
+        while let Some(index) = self.enemies.iter().position(|enemy| enemy.health <= 0) {
+            let enemy = self.enemies.swap_remove(index);
+            let first_drop = drops.len();
+            for effect in enemy.on_death.iter() {
+                self.apply_death_effect(effect, &enemy, player, &mut spawned, &mut drops);
+            }
+            // Line up multiple drops side by side instead of stacking them
+            let count = drops.len() - first_drop;
+            for (i, drop) in drops[first_drop..].iter_mut().enumerate() {
+                drop.position.x += (i as f32 - (count - 1) as f32 / 2.0) * DROP_SPREAD;
+            }
+        }
+        self.enemies.append(&mut spawned);
+        drops
+    }
+
+    fn apply_death_effect(
+        &mut self,
+        effect: &DeathEffect,
+        enemy: &Enemy,
+        player: &mut Player,
+        spawned: &mut Vec<Enemy>,
+        drops: &mut Vec<DroppedItem>,
+    ) {
+        match *effect {
+            DeathEffect::Explode {
+                radius,
+                player_damage,
+                enemy_damage,
+            } => {
+                let center = Vector2::new(enemy.position.x, enemy.position.y);
+                let player_center = Vector2::new(player.position.x, player.position.y);
+                if center.distance_to(player_center) <= radius + player.collision_radius {
+                    player.take_damage(player_damage);
+                }
+
+                for other in self.enemies.iter_mut() {
+                    let texture = self
+                        .texture_map
+                        .get(&other.enemy_type)
+                        .expect("unable to find texture");
+                    if other
+                        .get_collision_rect(texture)
+                        .check_collision_circle_rec(center, radius)
+                    {
+                        other.take_damage(enemy_damage, false);
+                    }
+                }
+
+                self.explosions.push(Explosion {
+                    position: enemy.position,
+                    radius,
+                    age: 0.0,
+                });
+            }
+            DeathEffect::Split { generation } => {
+                let mut rng = rand::rng();
+                let count = rng.random_range(SPLIT_COUNT_MIN..=SPLIT_COUNT_MAX);
+                // Spread the children evenly around the parent, starting at a random angle
+                let start_angle = rng.random_range(0.0..std::f32::consts::TAU);
+                for child in 0..count {
+                    let angle = start_angle + std::f32::consts::TAU * child as f32 / count as f32;
+                    let position = Position {
+                        x: enemy.position.x + angle.cos() * SPLIT_SPREAD,
+                        y: enemy.position.y + angle.sin() * SPLIT_SPREAD,
+                    };
+                    spawned.push(EnemyType::new_splitter(position, generation));
+                }
+            }
+            DeathEffect::Drop(kind) => drops.push(DroppedItem::new(kind, enemy.position)),
+        }
+    }
+
+    /// Throws every enemy within `radius` of `center` outwards. Bosses and
+    /// other knockback immune enemies stand their ground.
+    pub fn push_back(&mut self, center: Position, radius: f32) {
+        for enemy in self.enemies.iter_mut() {
+            let dx = enemy.position.x - center.x;
+            let dy = enemy.position.y - center.y;
+            let distance = (dx * dx + dy * dy).sqrt();
+            if enemy.knockback_immune || distance > radius || distance <= 0.0 {
+                continue;
+            }
+            enemy.velocity_x = dx / distance * SHOCKWAVE_SPEED;
+            enemy.velocity_y = dy / distance * SHOCKWAVE_SPEED;
+            enemy.knockback_cooldown = SHOCKWAVE_KNOCKBACK_TIME;
+        }
+
+        self.explosions.push(Explosion {
+            position: center,
+            radius,
+            age: 0.0,
+        });
+    }
+
+    /// Hands spawning over to the director script.
+    pub fn spawn_enemies(&mut self, delta: &f32, player_pos: &Position, elapsed_time: f32) {
+        let mut rng = rand::rng();
+        let mut spawned =
+            self.director
+                .update(*delta, elapsed_time, &self.enemies, player_pos, &mut rng);
+        self.enemies.append(&mut spawned);
+    }
+}
+
+fn handle_movement(player: &Player, enemy: &mut Enemy, delta: &f32) {
+    // Semi-implicit Euler integration
+
+    // Update knockback cooldown timer
+    if enemy.knockback_cooldown > 0.0 {
+        enemy.knockback_cooldown -= delta;
+    }
+
+    // Step 1: Apply friction to velocity
+    let friction = 1.0; // Aggressive friction per frame
+    enemy.velocity_x *= friction;
+    enemy.velocity_y *= friction;
+
+    // Step 2: Calculate direction vector to player
+    let dx = player.position.x - enemy.position.x;
+    let dy = player.position.y - enemy.position.y;
+
+    // Calculate distance (magnitude of direction vector)
+    let distance = (dx * dx + dy * dy).sqrt();
+
+    // Step 3: Ask the behaviour where to go, then update velocity
+    // (only if distance > 0 to avoid division by zero AND not in knockback state)
+    if distance > 0.0 && enemy.knockback_cooldown <= 0.0 {
+        let speed_multiplier = statuses::calculate_speed_multiplier(&enemy.statuses);
+        let health_fraction = enemy.health as f32 / enemy.max_health as f32;
+        let steering = enemy
+            .behaviour
+            .update(player, &enemy.position, health_fraction, *delta);
+
+        let mut speed_scale = speed_multiplier * steering.speed_scale;
+        if steering.speed_scale <= 0.0 {
+            let brake = (1.0 - BRAKE_RATE * delta).max(0.0);
+            enemy.velocity_x *= brake;
+            enemy.velocity_y *= brake;
+            // Still allow separation to nudge a stopped enemy at normal speed
+            speed_scale = speed_multiplier;
+        } else {
+            let acceleration_x = steering.direction_x * SPEED * speed_scale;
+            let acceleration_y = steering.direction_y * SPEED * speed_scale;
+
+            // Step 4: Update velocity with acceleration (semi-implicit Euler!)
+            enemy.velocity_x += acceleration_x * delta;
+            enemy.velocity_y += acceleration_y * delta;
+        }
+
+        // Flocking goes in before the clamp so crowds still respect the speed cap
+        enemy.velocity_x += enemy.flock_x * delta;
+        enemy.velocity_y += enemy.flock_y * delta;
+
+        let max_velocity = if steering.ignores_speed_cap {
+            MAX_VELOCITY * speed_scale
+        } else {
+            (MAX_VELOCITY * speed_scale * enemy.speed as f32 / BASE_ENEMY_SPEED).min(MAX_VELOCITY)
+        };
+        enemy.velocity_x = max_velocity.min(enemy.velocity_x).max(-max_velocity);
+        enemy.velocity_y = max_velocity.min(enemy.velocity_y).max(-max_velocity);
+    }
+
+    // Step 5: Update position using NEW velocity
+    enemy.position.x += enemy.velocity_x * delta;
+    enemy.position.y += enemy.velocity_y * delta;
+
+    // Step 6: Update direction based on velocity
+    if enemy.velocity_x.abs() > 0.1 {
+        enemy.direction = if enemy.velocity_x > 0.0 {
+            Direction::Right
+        } else {
+            Direction::Left
+        };
+    }
+}
+
+fn handle_player_collision<'a>(
+    texture_map: &HashMap<EnemyType, &'a Texture2D>,
+    player: &mut Player,
+    enemy: &mut Enemy,
+    delta: &f32,
+) {
+    let texture = texture_map
+        .get(&enemy.enemy_type)
+        .expect("unable to find texture");
+
+    let enemy_rec = enemy.get_collision_rect(texture);
+    let player_point = Vector2::new(player.position.x, player.position.y);
+
+    enemy.time_since_last_attack += delta;
+    if enemy_rec.check_collision_circle_rec(player_point, player.collision_radius) {
+        // Hits are held, not spent, while the player has i-frames
+        if enemy.time_since_last_attack >= enemy.attack_speed && !player.is_invulnerable() {
+            player.take_damage(enemy.damage);
+            affixes::handle_contact_hit(enemy, enemy.damage);
+            enemy.time_since_last_attack = 0.0;
+        }
+        // enemy.position IS the center (matches rendering origin)
+        let dx = enemy.position.x - player.position.x;
+        let dy = enemy.position.y - player.position.y;
+        let distance = (dx * dx + dy * dy).sqrt();
+
+        if distance > 0.0 && !enemy.knockback_immune {
+            let knockback_strength = 20000.0;
+            let knockback_duration = 0.2;
+
+            enemy.velocity_x += (dx / distance) * knockback_strength * delta;
+            enemy.velocity_y += (dy / distance) * knockback_strength * delta;
+
+            enemy.knockback_cooldown = knockback_duration;
+        }
+    }
+}
+
+/// Winds up and fires aimed volleys at the player.
+fn handle_ranged_attack(
+    player: &Player,
+    enemy: &mut Enemy,
+    delta: &f32,
+    shots: &mut Vec<Projectile>,
+) {
+    let position = enemy.position;
+    let can_attack = enemy.behaviour.can_attack();
+    let Some(ranged_attack) = enemy.ranged_attack.as_mut() else {
+        return;
+    };
+
+    let dx = player.position.x - position.x;
+    let dy = player.position.y - position.y;
+    let distance = (dx * dx + dy * dy).sqrt();
+
+    match ranged_attack.telegraph_remaining {
+        Some(remaining) if remaining - delta <= 0.0 => {
+            let spread = ranged_attack.volley_spread;
+            let half_volley = (ranged_attack.volley_size as f32 - 1.0) / 2.0;
+            for shot in 0..ranged_attack.volley_size {
+                let angle = ranged_attack.aim_angle + (shot as f32 - half_volley) * spread;
+                shots.push(Projectile::new_enemy_bolt(position, angle));
+            }
+            ranged_attack.telegraph_remaining = None;
+            ranged_attack.time_since_last_shot = 0.0;
+        }
+        Some(remaining) => {
+            ranged_attack.telegraph_remaining = Some(remaining - delta);
+        }
+        None => {
+            ranged_attack.time_since_last_shot += delta;
+            let in_range = distance <= ranged_attack.preferred_distance * RANGED_ENGAGE_FACTOR;
+            if can_attack
+                && in_range
+                && ranged_attack.time_since_last_shot >= ranged_attack.fire_interval
+            {
+                // Aim is locked in when the telegraph starts so it can be dodged
+                ranged_attack.aim_angle = dy.atan2(dx);
+                ranged_attack.telegraph_remaining = Some(ranged_attack.telegraph_time);
+            }
+        }
+    }
+}
+
+/// Fires the boss's current phase attack when its timer comes round.
+fn handle_boss_attack(
+    enemy: &mut Enemy,
+    delta: &f32,
+    shots: &mut Vec<Projectile>,
+    summoned: &mut Vec<Enemy>,
+) {
+    let health_fraction = enemy.health as f32 / enemy.max_health as f32;
+    let Some(boss) = enemy.boss.as_mut() else {
+        return;
+    };
+    let Some(attack) = boss.update(&mut enemy.behaviour, health_fraction, *delta) else {
+        return;
+    };
+
+    match attack {
+        BossAttack::BulletRing { count } => {
+            for shot in 0..count {
+                let angle = std::f32::consts::TAU * shot as f32 / count as f32;
+                shots.push(Projectile::new_enemy_bolt(enemy.position, angle));
+            }
+        }
+        BossAttack::SummonMinions { count } => {
+            for minion in 0..count {
+                let angle = std::f32::consts::TAU * minion as f32 / count as f32;
+                let position = Position {
+                    x: enemy.position.x + angle.cos() * BOSS_SUMMON_RADIUS,
+                    y: enemy.position.y + angle.sin() * BOSS_SUMMON_RADIUS,
+                };
+                summoned.push(EnemyType::new_servo_skull(position));
+            }
+        }
+    }
+}
+
+/// Something that happens when an enemy dies, however it was killed.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DeathEffect {
+    /// Damages the player and every other enemy inside `radius`.
+    Explode {
+        radius: f32,
+        player_damage: i32,
+        enemy_damage: i32,
+    },
+    /// Breaks into smaller copies of the given splitter generation.
+    Split { generation: u32 },
+    /// Leaves something behind for the player to pick up.
+    Drop(DropKind),
+}
+
+#[derive(Clone, Copy)]
+pub struct RangedAttack {
+    pub preferred_distance: f32,
+    pub fire_interval: f32,
+    pub time_since_last_shot: f32,
+    /// How long the shot is telegraphed before it fires.
+    pub telegraph_time: f32,
+    /// Set while a shot is winding up.
+    pub telegraph_remaining: Option<f32>,
+    pub aim_angle: f32,
+    pub volley_size: u32,
+    pub volley_spread: f32,
+}
+
+impl RangedAttack {
+    /// 0.0 when the telegraph starts, 1.0 when the shot fires.
+    pub fn get_telegraph_progress(&self) -> Option<f32> {
+        self.telegraph_remaining
+            .map(|remaining| 1.0 - (remaining / self.telegraph_time).clamp(0.0, 1.0))
+    }
+}
+
+pub struct Enemy {
+    pub enemy_type: EnemyType,
+
+    pub health: i32,
+    pub max_health: i32,
+    pub speed: i32,
+
+    pub damage: i32,
+    pub time_since_last_attack: f32,
+    pub attack_speed: f32,
+
+    pub position: Position,
+    pub direction: Direction,
+    pub velocity_x: f32,
+    pub velocity_y: f32,
+    /// Multiplies the sprite and collision size.
+    pub scale: f32,
+    /// Separation/flocking acceleration from nearby enemies, refreshed every tick.
+    pub flock_x: f32,
+    pub flock_y: f32,
+
+    pub knockback_cooldown: f32,
+    /// Heavy enemies shrug off the bounce back from hitting the player.
+    pub knockback_immune: bool,
+    /// Flat reduction applied to every hit, see `take_damage`.
+    pub armor: i32,
+
+    pub statuses: Vec<Status>,
+    pub behaviour: Behaviour,
+    /// `None` for enemies that only deal contact damage.
+    pub ranged_attack: Option<RangedAttack>,
+    pub on_death: Vec<DeathEffect>,
+    /// Phases and attack patterns, only set on bosses.
+    pub boss: Option<Boss>,
+    /// Affixes rolled at spawn, `None` for regular enemies.
+    pub elite: Option<Elite>,
+}
+
+impl Enemy {
+    /// Centered on enemy.position to match visual rendering
+    /// (renderer uses origin offset to center sprite on position)
+    pub fn get_collision_rect(&self, texture: &Texture2D) -> Rectangle {
+        let width = texture.width as f32 * self.scale;
+        let height = texture.height as f32 * self.scale;
+        Rectangle::new(
+            self.position.x - width / 2.0,
+            self.position.y - height / 2.0,
+            width,
+            height,
+        )
+    }
+
+    /// Armor is taken off each hit, so it hurts weapons that land many small
+    /// hits the most. `pierces_armor` skips it entirely. Elite shields soak
+    /// whatever gets through before health does.
+    pub fn take_damage(&mut self, amount: i32, pierces_armor: bool) {
+        let mut damage = if pierces_armor || self.armor <= 0 {
+            amount
+        } else {
+            (amount - self.armor).max(MIN_ARMORED_DAMAGE)
+        };
+        if let Some(elite) = self.elite.as_mut() {
+            let absorbed = damage.min(elite.shield);
+            elite.shield -= absorbed;
+            damage -= absorbed;
+        }
+        self.health -= damage;
+    }
+
+    pub fn add_status(&mut self, status: Status) {
+        statuses::add_status(&mut self.statuses, status);
+    }
+
+    pub fn handle_status_effects(&mut self, delta: &f32) {
+        for status in self.statuses.iter_mut() {
+            self.health += status.tick(*delta);
+        }
+        self.health = self.health.min(self.max_health);
+
+        self.statuses.retain(|status| !status.is_expired());
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnemyType {
+    ServoSkull,
+    DarkFighter,
+    Exploder,
+    Tank,
+    Splitter,
+    Boss,
+}
+
+impl EnemyType {
+    pub fn servo_skull_type() -> EnemyType {
+        EnemyType::ServoSkull
+    }
+
+    pub fn new_servo_skull(position: Position) -> Enemy {
+        Enemy {
+            enemy_type: EnemyType::ServoSkull,
+            health: 10,
+            max_health: 30,
+            speed: 450,
+            damage: 10,
+            time_since_last_attack: 0.0,
+            attack_speed: 1.0,
+            direction: Direction::Right,
+            position,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+            scale: 1.0,
+            flock_x: 0.0,
+            flock_y: 0.0,
+            knockback_cooldown: 0.0,
+            knockback_immune: false,
+            armor: 0,
+            statuses: vec![],
+            behaviour: Behaviour::new(BehaviourState::Chase),
+            ranged_attack: None,
+            on_death: vec![],
+            boss: None,
+            elite: None,
+        }
+    }
+
+    /// A strafing gunship that keeps its distance and fires aimed volleys.
+    pub fn new_dark_fighter(position: Position) -> Enemy {
+        Enemy {
+            enemy_type: EnemyType::DarkFighter,
+            health: 40,
+            max_health: 40,
+            speed: 300,
+            damage: 10,
+            time_since_last_attack: 0.0,
+            attack_speed: 1.0,
+            direction: Direction::Right,
+            position,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+            scale: 1.0,
+            flock_x: 0.0,
+            flock_y: 0.0,
+            knockback_cooldown: 0.0,
+            knockback_immune: false,
+            armor: 0,
+            statuses: vec![],
+            behaviour: Behaviour::new(BehaviourState::keep_distance(450.0)).with_flee_below(0.25),
+            ranged_attack: Some(RangedAttack {
+                preferred_distance: 450.0,
+                fire_interval: 2.5,
+                time_since_last_shot: 0.0,
+                telegraph_time: 0.6,
+                telegraph_remaining: None,
+                aim_angle: 0.0,
+                volley_size: 3,
+                volley_spread: 8.0_f32.to_radians(),
+            }),
+            on_death: vec![],
+            boss: None,
+            elite: None,
+        }
+    }
+
+    /// Rushes the player, stops to flash, then blows up. Killing it early
+    /// sets it off too.
+    pub fn new_exploder(position: Position) -> Enemy {
+        Enemy {
+            enemy_type: EnemyType::Exploder,
+            health: 15,
+            max_health: 15,
+            speed: 500,
+            damage: 0,
+            time_since_last_attack: 0.0,
+            attack_speed: 1.0,
+            direction: Direction::Right,
+            position,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+            scale: 1.0,
+            flock_x: 0.0,
+            flock_y: 0.0,
+            knockback_cooldown: 0.0,
+            knockback_immune: false,
+            armor: 0,
+            statuses: vec![],
+            behaviour: Behaviour::new(BehaviourState::fuse()),
+            ranged_attack: None,
+            on_death: vec![DeathEffect::Explode {
+                radius: 160.0,
+                player_damage: 30,
+                enemy_damage: 40,
+            }],
+            boss: None,
+            elite: None,
+        }
+    }
+
+    /// Slow, heavily armoured brute that walks straight through knockback
+    /// and hits hard when it connects. Wanders until the player gets close.
+    pub fn new_tank(position: Position) -> Enemy {
+        Enemy {
+            enemy_type: EnemyType::Tank,
+            health: 150,
+            max_health: 150,
+            speed: 200,
+            damage: 30,
+            time_since_last_attack: 0.0,
+            attack_speed: 2.5,
+            direction: Direction::Right,
+            position,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+            scale: 1.0,
+            flock_x: 0.0,
+            flock_y: 0.0,
+            knockback_cooldown: 0.0,
+            knockback_immune: true,
+            armor: 6,
+            statuses: vec![],
+            behaviour: Behaviour::new(BehaviourState::Chase).with_aggro_range(TANK_AGGRO_RANGE),
+            ranged_attack: None,
+            on_death: vec![],
+            boss: None,
+            elite: None,
+        }
+    }
+
+    /// Crescent moon alien that splits into smaller, faster copies when
+    /// killed, until it reaches `SPLITTER_MAX_GENERATION`.
+    pub fn new_splitter(position: Position, generation: u32) -> Enemy {
+        let scale = SPLIT_SCALE.powi(generation as i32);
+        let health = (40.0 * scale * scale).ceil() as i32;
+        let on_death = if generation < SPLITTER_MAX_GENERATION {
+            vec![DeathEffect::Split {
+                generation: generation + 1,
+            }]
+        } else {
+            vec![]
+        };
+
+        Enemy {
+            enemy_type: EnemyType::Splitter,
+            health,
+            max_health: health,
+            speed: (350.0 * SPLIT_SPEED_FACTOR.powi(generation as i32)) as i32,
+            damage: 8,
+            time_since_last_attack: 0.0,
+            attack_speed: 1.0,
+            direction: Direction::Right,
+            position,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+            scale,
+            flock_x: 0.0,
+            flock_y: 0.0,
+            knockback_cooldown: 0.0,
+            knockback_immune: false,
+            armor: 0,
+            statuses: vec![],
+            behaviour: Behaviour::new(BehaviourState::Chase),
+            ranged_attack: None,
+            on_death,
+            boss: None,
+            elite: None,
+        }
+    }
+
+    /// A huge tanker that works through `Boss` phases and always leaves a
+    /// reward chest behind. `tier` is how many bosses came before it.
+    pub fn new_boss(position: Position, tier: usize) -> Enemy {
+        let boss = Boss::new(tier);
+        let health = BOSS_BASE_HEALTH * (tier as i32 + 1);
+        Enemy {
+            enemy_type: EnemyType::Boss,
+            health,
+            max_health: health,
+            speed: 250,
+            damage: 40,
+            time_since_last_attack: 0.0,
+            attack_speed: 2.0,
+            direction: Direction::Right,
+            position,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+            scale: BOSS_SCALE,
+            flock_x: 0.0,
+            flock_y: 0.0,
+            knockback_cooldown: 0.0,
+            knockback_immune: true,
+            armor: 4,
+            statuses: vec![],
+            behaviour: Behaviour::new(boss.get_phase().movement),
+            ranged_attack: None,
+            on_death: vec![
+                DeathEffect::Drop(DropKind::RewardChest),
+                DeathEffect::Drop(DropKind::EmperorsBlessing),
+            ],
+            boss: Some(boss),
+            elite: None,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    // Helper function to create a simple player for testing
+    pub(crate) fn create_test_player(x: f32, y: f32) -> Player {
+        Player::new(Position { x, y }, fake_texture(32))
+    }
+
+    /// Square texture that only carries a size, so tests don't need a window.
+    pub(crate) fn fake_texture(size: i32) -> Texture2D {
+        unsafe {
+            Texture2D::from_raw(raylib::ffi::Texture2D {
+                id: 0,
+                width: size,
+                height: size,
+                mipmaps: 1,
+                format: 0,
+            })
+        }
+    }
+
+    /// An empty horde where every enemy type is drawn with `texture`.
+    pub(crate) fn create_test_enemies(texture: &Texture2D) -> AllEnemies<'_> {
+        AllEnemies::new(
+            EnemyTextures {
+                servo_skull: texture,
+                dark_fighter: texture,
+                dark_tanker: texture,
+                crescent_moon: texture,
+            },
+            DirectorScript::default(),
+        )
+    }
+
+    #[test]
+    fn test_semi_implicit_euler_movement() {
+        // Create a player at (100, 100)
+        let player = create_test_player(100.0, 100.0);
+
+        // Create an enemy at (0, 0) with zero initial velocity
+        let mut enemy = EnemyType::new_servo_skull(Position { x: 0.0, y: 0.0 });
+
+        let delta = 0.1; // 100ms time step
+
+        println!("Initial state:");
+        println!(
+            "  Enemy position: ({}, {})",
+            enemy.position.x, enemy.position.y
+        );
+        println!(
+            "  Enemy velocity: ({}, {})",
+            enemy.velocity_x, enemy.velocity_y
+        );
+
+        // Call handle_movement
+        handle_movement(&player, &mut enemy, &delta);
+
+        println!("\nAfter first frame:");
+        println!(
+            "  Enemy position: ({}, {})",
+            enemy.position.x, enemy.position.y
+        );
+        println!(
+            "  Enemy velocity: ({}, {})",
+            enemy.velocity_x, enemy.velocity_y
+        );
+
+        // Verify that the enemy moved toward the player
+        assert!(
+            enemy.position.x > 0.0,
+            "Enemy should move in positive x direction toward player"
+        );
+        assert!(
+            enemy.position.y > 0.0,
+            "Enemy should move in positive y direction toward player"
+        );
+
+        // Verify that velocity was updated
+        assert!(
+            enemy.velocity_x > 0.0,
+            "Enemy velocity_x should be positive"
+        );
+        assert!(
+            enemy.velocity_y > 0.0,
+            "Enemy velocity_y should be positive"
+        );
+
+        // Store position after first frame
+        let x_after_1 = enemy.position.x;
+        let y_after_1 = enemy.position.y;
+
+        // Run another frame
+        handle_movement(&player, &mut enemy, &delta);
+
+        println!("\nAfter second frame:");
+        println!(
+            "  Enemy position: ({}, {})",
+            enemy.position.x, enemy.position.y
+        );
+        println!(
+            "  Enemy velocity: ({}, {})",
+            enemy.velocity_x, enemy.velocity_y
+        );
+
+        // Enemy should continue moving toward player
+        assert!(
+            enemy.position.x > x_after_1,
+            "Enemy should continue moving in positive x"
+        );
+        assert!(
+            enemy.position.y > y_after_1,
+            "Enemy should continue moving in positive y"
+        );
+    }
+
+    #[test]
+    fn test_velocity_accumulation() {
+        let player = create_test_player(1000.0, 0.0);
+
+        let mut enemy = EnemyType::new_servo_skull(Position { x: 0.0, y: 0.0 });
+
+        let delta = 0.016; // ~60fps
+
+        // Run several frames
+        for i in 0..10 {
+            handle_movement(&player, &mut enemy, &delta);
+            println!(
+                "Frame {}: pos=({:.2}, {:.2}), vel=({:.2}, {:.2})",
+                i, enemy.position.x, enemy.position.y, enemy.velocity_x, enemy.velocity_y
+            );
+        }
+
+        // After 10 frames, the enemy should have moved significantly
+        assert!(
+            enemy.position.x > 5.0,
+            "Enemy should have moved significantly after 10 frames (got x={})",
+            enemy.position.x
+        );
+
+        // Velocity should be non-zero and substantial
+        assert!(
+            enemy.velocity_x.abs() > 10.0,
+            "Enemy should have accumulated substantial velocity (got vx={})",
+            enemy.velocity_x
+        );
+    }
+
+    #[test]
+    fn test_friction_effect() {
+        // Place player far away so acceleration is minimal
+        let player = create_test_player(100.0, 100.0);
+
+        let mut enemy = EnemyType::new_servo_skull(Position { x: 100.0, y: 100.0 });
+
+        // Give enemy initial velocity in x direction (perpendicular to player)
+        enemy.velocity_x = 100.0;
+        enemy.velocity_y = 0.0;
+
+        let delta = 0.016;
+
+        println!(
+            "Initial velocity: ({}, {})",
+            enemy.velocity_x, enemy.velocity_y
+        );
+
+        let initial_vx = enemy.velocity_x;
+
+        // Run several frames - friction should reduce velocity
+        for i in 0..3 {
+            let prev_vx = enemy.velocity_x;
+            handle_movement(&player, &mut enemy, &delta);
+            println!(
+                "Frame {}: vel=({:.2}, {:.2})",
+                i, enemy.velocity_x, enemy.velocity_y
+            );
+            // Each frame should reduce velocity
+            assert!(
+                enemy.velocity_x < prev_vx,
+                "Frame {}: Friction should reduce velocity each frame",
+                i
+            );
+        }
+
+        // Velocity should have decreased due to friction
+        assert!(
+            enemy.velocity_x < initial_vx,
+            "Friction should reduce velocity_x from {} to {}",
+            initial_vx,
+            enemy.velocity_x
+        );
+    }
+
+    // Helper to create a collision rectangle centered on a position (matches rendering)
+    fn create_centered_enemy_rect(pos_x: f32, pos_y: f32, width: f32, height: f32) -> Rectangle {
+        Rectangle::new(pos_x - width / 2.0, pos_y - height / 2.0, width, height)
+    }
+
+    #[test]
+    fn test_enemy_collision_rectangle_centered() {
+        // Create an enemy at position (100, 100) with a 64x64 texture
+        let enemy_pos_x = 100.0;
+        let enemy_pos_y = 100.0;
+        let texture_width = 64.0;
+        let texture_height = 64.0;
+
+        let rect =
+            create_centered_enemy_rect(enemy_pos_x, enemy_pos_y, texture_width, texture_height);
+
+        // The collision rectangle should be centered on enemy position
+        // For a 64x64 texture at (100, 100):
+        // - Top-left should be at (100 - 32, 100 - 32) = (68, 68)
+        // - Bottom-right should be at (68 + 64, 68 + 64) = (132, 132)
+        assert_eq!(rect.x, 68.0, "Rectangle X should be position - half_width");
+        assert_eq!(rect.y, 68.0, "Rectangle Y should be position - half_height");
+        assert_eq!(rect.width, 64.0, "Rectangle width should match texture");
+        assert_eq!(rect.height, 64.0, "Rectangle height should match texture");
+
+        // Verify the center of the rectangle is at the enemy position
+        let rect_center_x = rect.x + rect.width / 2.0;
+        let rect_center_y = rect.y + rect.height / 2.0;
+        assert_eq!(
+            rect_center_x, enemy_pos_x,
+            "Rectangle center X should match enemy position"
+        );
+        assert_eq!(
+            rect_center_y, enemy_pos_y,
+            "Rectangle center Y should match enemy position"
+        );
+    }
+
+    #[test]
+    fn test_player_enemy_collision_at_boundary() {
+        // Player at origin with known collision radius
+        let player = create_test_player(0.0, 0.0);
+        let player_radius = player.collision_radius;
+
+        // Enemy texture size (simulated)
+        let enemy_half_width = 32.0; // 64x64 texture
+
+        // Place enemy so its left edge just touches player's collision circle
+        // Enemy center should be at: player_radius + enemy_half_width
+        let boundary_distance = player_radius + enemy_half_width;
+
+        let enemy_rect = create_centered_enemy_rect(boundary_distance, 0.0, 64.0, 64.0);
+        let player_point = Vector2::new(0.0, 0.0);
+
+        // At exactly the boundary, collision should occur (edges touching)
+        let collides_at_boundary =
+            enemy_rect.check_collision_circle_rec(player_point, player_radius);
+        assert!(
+            collides_at_boundary,
+            "Collision should occur when enemy edge touches player circle (distance={})",
+            boundary_distance
+        );
+
+        // One pixel inside boundary should definitely collide
+        let enemy_rect_inside =
+            create_centered_enemy_rect(boundary_distance - 1.0, 0.0, 64.0, 64.0);
+        let collides_inside =
+            enemy_rect_inside.check_collision_circle_rec(player_point, player_radius);
+        assert!(
+            collides_inside,
+            "Collision should occur when enemy is inside boundary"
+        );
+    }
+
+    #[test]
+    fn test_no_collision_when_not_touching() {
+        // Player at origin
+        let player = create_test_player(0.0, 0.0);
+        let player_radius = player.collision_radius;
+
+        // Enemy texture size (simulated)
+        let enemy_half_width = 32.0; // 64x64 texture
+
+        // Place enemy well outside collision range
+        // Gap of 10 pixels between player circle and enemy rectangle
+        let safe_distance = player_radius + enemy_half_width + 10.0;
+
+        let enemy_rect = create_centered_enemy_rect(safe_distance, 0.0, 64.0, 64.0);
+        let player_point = Vector2::new(0.0, 0.0);
+
+        let collides = enemy_rect.check_collision_circle_rec(player_point, player_radius);
+        assert!(
+            !collides,
+            "No collision should occur when enemy is {} pixels away (gap of 10px)",
+            safe_distance
+        );
+
+        // Test diagonal case - enemy far away diagonally
+        let enemy_rect_diagonal =
+            create_centered_enemy_rect(safe_distance, safe_distance, 64.0, 64.0);
+        let collides_diagonal =
+            enemy_rect_diagonal.check_collision_circle_rec(player_point, player_radius);
+        assert!(
+            !collides_diagonal,
+            "No collision should occur when enemy is diagonally away"
+        );
+    }
+
+    #[test]
+    fn test_ranged_enemy_strafes_at_preferred_distance() {
+        // Fighter sits exactly at its preferred distance to the right of the player
+        let player = create_test_player(0.0, 0.0);
+        let mut enemy = EnemyType::new_dark_fighter(Position { x: 450.0, y: 0.0 });
+
+        handle_movement(&player, &mut enemy, &0.1);
+
+        // No pull towards or away from the player, only sideways movement
+        assert!(
+            enemy.velocity_x.abs() < 0.001,
+            "Expected no radial velocity, got {}",
+            enemy.velocity_x
+        );
+        assert!(
+            enemy.velocity_y.abs() > 0.0,
+            "Expected the fighter to strafe around the player"
+        );
+    }
+
+    #[test]
+    fn test_ranged_enemy_telegraphs_before_firing() {
+        let player = create_test_player(0.0, 0.0);
+        let mut enemy = EnemyType::new_dark_fighter(Position { x: 450.0, y: 0.0 });
+        let mut shots = vec![];
+
+        // Fire interval elapses, the shot starts winding up instead of firing
+        handle_ranged_attack(&player, &mut enemy, &2.5, &mut shots);
+        assert!(shots.is_empty(), "Shot should be telegraphed first");
+        assert!(enemy.ranged_attack.unwrap().telegraph_remaining.is_some());
+
+        // Once the telegraph runs out the volley is fired
+        handle_ranged_attack(&player, &mut enemy, &0.6, &mut shots);
+        assert_eq!(shots.len(), 3);
+        assert!(enemy.ranged_attack.unwrap().telegraph_remaining.is_none());
+    }
+
+    #[test]
+    fn test_velocity_cap_holds_for_fast_and_hasted_enemies() {
+        let player = create_test_player(5000.0, 5000.0);
+        let mut enemy = EnemyType::new_splitter(Position { x: 0.0, y: 0.0 }, 3);
+        enemy.add_status(Status::SpeedBoost(statuses::SpeedBoostStatus {
+            speed_multiplier: 1.5,
+            remaining_duration: f32::INFINITY,
+        }));
+        enemy.flock_x = 500.0;
+        enemy.flock_y = 500.0;
+
+        for _ in 0..60 {
+            handle_movement(&player, &mut enemy, &0.016);
+            assert!(enemy.velocity_x.abs() <= MAX_VELOCITY);
+            assert!(enemy.velocity_y.abs() <= MAX_VELOCITY);
+        }
+        assert_eq!(enemy.velocity_x, MAX_VELOCITY, "Should reach the cap");
+    }
+
+    #[test]
+    fn test_exploder_death_damages_player_and_nearby_enemies() {
+        let texture = fake_texture(64);
+        let mut all_enemies = create_test_enemies(&texture);
+        let mut player = create_test_player(0.0, 0.0);
+
+        // Exploder killed early, with one skull caught in the blast and one far away
+        let mut exploder = EnemyType::new_exploder(Position { x: 50.0, y: 0.0 });
+        exploder.health = 0;
+        all_enemies.enemies.push(exploder);
+        all_enemies
+            .enemies
+            .push(EnemyType::new_servo_skull(Position { x: 150.0, y: 0.0 }));
+        all_enemies
+            .enemies
+            .push(EnemyType::new_servo_skull(Position { x: 1000.0, y: 0.0 }));
+
+        all_enemies.handle_deaths(&mut player);
+
+        assert_eq!(player.health, 70, "Player should take the blast damage");
+        assert_eq!(
+            all_enemies.enemies.len(),
+            1,
+            "Skull in the blast should die with the exploder"
+        );
+        assert_eq!(all_enemies.enemies[0].position.x, 1000.0);
+        assert_eq!(all_enemies.explosions.len(), 1);
+    }
+
+    #[test]
+    fn test_armor_reduces_small_hits_more() {
+        let mut tank = EnemyType::new_tank(Position { x: 0.0, y: 0.0 });
+
+        tank.take_damage(8, false);
+        assert_eq!(tank.health, 148, "Armor should soak most of a pellet");
+
+        tank.take_damage(2, false);
+        assert_eq!(tank.health, 147, "Hits always do at least some damage");
+
+        tank.take_damage(2, true);
+        assert_eq!(tank.health, 145, "Piercing damage ignores armor");
+    }
+
+    #[test]
+    fn test_tank_wanders_until_player_is_close() {
+        let mut tank = EnemyType::new_tank(Position { x: 0.0, y: 0.0 });
+
+        let far_player = create_test_player(TANK_AGGRO_RANGE + 500.0, 0.0);
+        handle_movement(&far_player, &mut tank, &0.016);
+        assert!(matches!(
+            tank.behaviour.state,
+            BehaviourState::Wander { .. }
+        ));
+
+        let near_player = create_test_player(TANK_AGGRO_RANGE - 100.0, 0.0);
+        handle_movement(&near_player, &mut tank, &0.016);
+        assert_eq!(tank.behaviour.state, BehaviourState::Chase);
+    }
+
+    #[test]
+    fn test_tank_ignores_knockback() {
+        let mut player = create_test_player(0.0, 0.0);
+        let texture = fake_texture(128);
+        let all_enemies = create_test_enemies(&texture);
+
+        let mut tank = EnemyType::new_tank(Position { x: 20.0, y: 0.0 });
+        tank.time_since_last_attack = tank.attack_speed;
+        handle_player_collision(&all_enemies.texture_map, &mut player, &mut tank, &0.016);
+
+        assert_eq!(player.health, 70, "Tank should land a heavy hit");
+        assert_eq!(tank.velocity_x, 0.0);
+        assert_eq!(tank.knockback_cooldown, 0.0);
+    }
+
+    #[test]
+    fn test_splitter_spawns_children_until_depth_limit() {
+        let texture = fake_texture(64);
+        let mut all_enemies = create_test_enemies(&texture);
+        let mut player = create_test_player(0.0, 0.0);
+
+        let mut splitter = EnemyType::new_splitter(Position { x: 500.0, y: 0.0 }, 0);
+        splitter.health = 0;
+        all_enemies.enemies.push(splitter);
+        all_enemies.handle_deaths(&mut player);
+
+        let children = all_enemies.enemies.len();
+        assert!((2..=3).contains(&children));
+        for child in all_enemies.enemies.iter() {
+            assert!(child.scale < 1.0, "Children should be smaller");
+            assert!(child.speed > 350, "Children should be faster");
+            assert!(child.health > 0, "Children should spawn alive");
+        }
+
+        // The last generation dies for good
+        let mut last =
+            EnemyType::new_splitter(Position { x: 500.0, y: 0.0 }, SPLITTER_MAX_GENERATION);
+        assert!(last.on_death.is_empty());
+        last.health = 0;
+        all_enemies.enemies = vec![last];
+        all_enemies.handle_deaths(&mut player);
+        assert!(all_enemies.enemies.is_empty());
+    }
+
+    #[test]
+    fn test_no_contact_damage_while_invulnerable() {
+        let mut player = create_test_player(0.0, 0.0);
+        player.invulnerable_remaining = 0.2;
+        let texture = fake_texture(64);
+        let all_enemies = create_test_enemies(&texture);
+
+        let mut enemy = EnemyType::new_servo_skull(Position { x: 10.0, y: 0.0 });
+        enemy.time_since_last_attack = enemy.attack_speed;
+        handle_player_collision(&all_enemies.texture_map, &mut player, &mut enemy, &0.016);
+
+        assert_eq!(player.health, 100);
+        assert!(
+            enemy.time_since_last_attack >= enemy.attack_speed,
+            "The hit should still be ready once the i-frames end"
+        );
+    }
+
+    #[test]
+    fn test_overlapping_enemies_only_hit_once() {
+        let mut player = create_test_player(0.0, 0.0);
+        let texture = fake_texture(64);
+        let all_enemies = create_test_enemies(&texture);
+
+        for _ in 0..10 {
+            let mut enemy = EnemyType::new_servo_skull(Position { x: 10.0, y: 0.0 });
+            enemy.time_since_last_attack = enemy.attack_speed;
+            handle_player_collision(&all_enemies.texture_map, &mut player, &mut enemy, &0.016);
+        }
+
+        assert_eq!(player.health, 90, "Only the first skull should land");
+        assert!(player.is_invulnerable());
+    }
+}
@@ -1,8 +1,14 @@
 use raylib::prelude::{RaylibShader, *};
 
 use crate::{
-    enemy::AllEnemies, player::Player, projectiles::AllProjectiles,
-    renderer::background::Background, weapon_pickups::AllWeaponPickups,
+    drops::AllDrops,
+    enemy::AllEnemies,
+    input::{Action, InputMap},
+    menu::PauseMenu,
+    player::{Player, REVIVE_SHOCKWAVE_RADIUS},
+    projectiles::{AllProjectiles, EnemyProjectiles},
+    renderer::background::Background,
+    weapon_pickups::AllWeaponPickups,
 };
 
 pub const DEBUG_MODE: bool = true;
@@ -11,9 +17,13 @@ pub struct GameState<'a> {
     pub rl: &'a mut raylib::RaylibHandle,
     pub player: Player,
     pub projectiles: AllProjectiles<'a>,
+    pub enemy_projectiles: EnemyProjectiles,
     pub enemies: AllEnemies<'a>,
     pub background: Background<'a>,
     pub weapon_pickups: AllWeaponPickups,
+    pub drops: AllDrops,
+    pub pause_menu: PauseMenu,
+    pub input_map: InputMap,
     pub white_texture: Texture2D,
     pub multi_melta_shader: MultiMeltaShader,
     pub elapsed_time: f32,
@@ -35,7 +45,7 @@ impl<'a> GameState<'a> {
         rl: &'a mut raylib::RaylibHandle,
         thread: &raylib::RaylibThread,
         player: Player,
-        enemy_texture: &'a Texture2D,
+        enemies: AllEnemies<'a>,
         bullet_texture: &'a Texture2D,
         ground_texture1: &'a Texture2D,
         ground_texture2: &'a Texture2D,
@@ -58,15 +68,21 @@ impl<'a> GameState<'a> {
         let color_mid_loc = multi_melta_shader.get_shader_location("color_mid");
         let color_cool_loc = multi_melta_shader.get_shader_location("color_cool");
 
-        let weapon_pickups = AllWeaponPickups::new(player.position);
+        let weapon_pickups = AllWeaponPickups::new(&player);
+        let mut projectiles = AllProjectiles::new(bullet_texture);
+        projectiles.append(&mut player.class.spawn_companions(player.position));
 
         GameState {
             rl,
             player,
-            projectiles: AllProjectiles::new(bullet_texture),
-            enemies: AllEnemies::new(enemy_texture),
+            projectiles,
+            enemy_projectiles: EnemyProjectiles::new(),
+            enemies,
             background: Background::new(ground_texture1, ground_texture2),
             weapon_pickups,
+            drops: AllDrops::new(),
+            pause_menu: PauseMenu::new(),
+            input_map: InputMap::new(),
             white_texture,
             multi_melta_shader: MultiMeltaShader {
                 shader: multi_melta_shader,
@@ -82,6 +98,14 @@ impl<'a> GameState<'a> {
         }
     }
 
+    /// Swaps in the player's controls. Any error from loading them waits on
+    /// the Controls screen.
+    pub fn with_input_map(mut self, input_map: InputMap, config_error: Option<String>) -> Self {
+        self.input_map = input_map;
+        self.pause_menu.config_error = config_error;
+        self
+    }
+
     pub fn player_alive(&self) -> bool {
         self.player.is_alive()
     }
@@ -101,7 +125,22 @@ impl<'a> GameState<'a> {
         }
     }
 
+    /// False once the player has picked Quit from the pause menu.
+    pub fn is_running(&self) -> bool {
+        !self.pause_menu.quit_requested
+    }
+
     pub fn game_tick(&mut self, delta: &f32) {
+        // Nothing moves, and the clock stops, while the pause menu is up
+        if self.pause_menu.open {
+            self.pause_menu.update(self.rl, &mut self.input_map);
+            return;
+        }
+        if self.input_map.is_pressed(self.rl, Action::Pause) {
+            self.pause_menu.toggle();
+            return;
+        }
+
         self.elapsed_time += delta;
 
         // Update player aim direction based on mouse BEFORE processing input
@@ -110,20 +149,44 @@ impl<'a> GameState<'a> {
         let camera = self.get_camera(screen_width, screen_height);
         self.player.update_aim_direction(self.rl, camera);
 
-        self.player.handle_user_input(self.rl, &delta);
+        self.player
+            .handle_user_input(self.rl, &self.input_map, &delta);
         self.player.handle_status_effects(&delta);
-        self.weapon_pickups.update(&mut self.player);
+        self.player.handle_shield(delta);
+        self.weapon_pickups.update(&mut self.player, delta);
+        self.drops.update(&mut self.player, delta);
 
         // Move enemy tick BEFORE handle_enemies so knockback velocity is applied next frame
-        self.enemies.tick(&mut self.player, &delta);
+        let mut enemy_shots = self.enemies.tick(&mut self.player, &delta);
+        self.enemy_projectiles.append(&mut enemy_shots);
         self.enemies
             .spawn_enemies(&delta, &self.player.position, self.elapsed_time);
 
         // handle and update projectiles
         // TODO I need to clean up projectiles that are passed the end of the play area!!
-        let mut new_projectiles = self.player.handle_weapons(&delta);
+        let mut new_projectiles = self.player.handle_weapons(&self.enemies, &delta);
         self.projectiles.append(&mut new_projectiles);
-        self.projectiles.move_projectiles(&self.player, &delta);
-        self.projectiles.handle_collision(&mut self.enemies);
+        let mut ability_projectiles = self
+            .player
+            .handle_abilities(self.rl, &self.input_map, delta);
+        self.projectiles.append(&mut ability_projectiles);
+        self.projectiles
+            .move_projectiles(&self.player, &self.enemies, &delta);
+        self.projectiles
+            .handle_collision(&mut self.enemies, &mut self.player);
+
+        self.enemy_projectiles
+            .move_projectiles(&self.player, &self.enemies, &delta);
+        self.enemy_projectiles.handle_collision(&mut self.player);
+
+        // Everything that can hurt enemies has run, clear out the dead
+        let mut drops = self.enemies.handle_deaths(&mut self.player);
+        self.drops.append(&mut drops);
+
+        // Last, so every source of damage this frame counts towards a lethal blow
+        if self.player.handle_death_saves(*delta) {
+            self.enemies
+                .push_back(self.player.position, REVIVE_SHOCKWAVE_RADIUS);
+        }
     }
 }
@@ -0,0 +1,673 @@
+use std::{collections::HashMap, fs, str::FromStr};
+
+use raylib::ffi::{GamepadAxis, GamepadButton, KeyboardKey, MouseButton};
+use raylib::prelude::*;
+
+pub const CONTROLS_CONFIG_PATH: &str = "./assests/data/controls.txt";
+
+/// Only the first connected gamepad is read.
+pub const GAMEPAD: i32 = 0;
+/// How far out the crosshair sits when aiming with the right stick.
+pub const GAMEPAD_AIM_DISTANCE: f32 = 200.0;
+// Stick travel below this is ignored so worn sticks don't drift
+const STICK_DEADZONE: f32 = 0.2;
+
+/// Whatever the player last aimed with. Decides where the crosshair is drawn.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum InputDevice {
+    KeyboardMouse,
+    Gamepad,
+}
+
+/// Rescales a raw stick reading so the edge of the deadzone reads as 0.0
+/// and full tilt reads as 1.0.
+pub fn apply_deadzone(stick: Vector2) -> Vector2 {
+    let length = stick.length();
+    if length < STICK_DEADZONE {
+        return Vector2::zero();
+    }
+    let scaled = ((length - STICK_DEADZONE) / (1.0 - STICK_DEADZONE)).min(1.0);
+    stick / length * scaled
+}
+
+fn read_stick(rl: &RaylibHandle, x_axis: GamepadAxis, y_axis: GamepadAxis) -> Vector2 {
+    if !rl.is_gamepad_available(GAMEPAD) {
+        return Vector2::zero();
+    }
+    apply_deadzone(Vector2::new(
+        rl.get_gamepad_axis_movement(GAMEPAD, x_axis),
+        rl.get_gamepad_axis_movement(GAMEPAD, y_axis),
+    ))
+}
+
+pub fn get_left_stick(rl: &RaylibHandle) -> Vector2 {
+    read_stick(
+        rl,
+        GamepadAxis::GAMEPAD_AXIS_LEFT_X,
+        GamepadAxis::GAMEPAD_AXIS_LEFT_Y,
+    )
+}
+
+pub fn get_right_stick(rl: &RaylibHandle) -> Vector2 {
+    read_stick(
+        rl,
+        GamepadAxis::GAMEPAD_AXIS_RIGHT_X,
+        GamepadAxis::GAMEPAD_AXIS_RIGHT_Y,
+    )
+}
+
+pub fn is_gamepad_button_pressed(rl: &RaylibHandle, button: GamepadButton) -> bool {
+    rl.is_gamepad_available(GAMEPAD) && rl.is_gamepad_button_pressed(GAMEPAD, button)
+}
+
+pub fn is_gamepad_button_down(rl: &RaylibHandle, button: GamepadButton) -> bool {
+    rl.is_gamepad_available(GAMEPAD) && rl.is_gamepad_button_down(GAMEPAD, button)
+}
+
+/// Something the player can do. Gameplay code asks the `InputMap` about
+/// actions rather than checking keys directly. The sticks always move and aim.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Dash,
+    Ability1,
+    Ability2,
+    Fire,
+    ToggleFireMode,
+    CycleAimSlot1,
+    CycleAimSlot2,
+    CycleAimSlot3,
+    Pause,
+    MenuUp,
+    MenuDown,
+    MenuConfirm,
+    MenuBack,
+}
+
+pub const ALL_ACTIONS: [Action; 17] = [
+    Action::MoveUp,
+    Action::MoveDown,
+    Action::MoveLeft,
+    Action::MoveRight,
+    Action::Dash,
+    Action::Ability1,
+    Action::Ability2,
+    Action::Fire,
+    Action::ToggleFireMode,
+    Action::CycleAimSlot1,
+    Action::CycleAimSlot2,
+    Action::CycleAimSlot3,
+    Action::Pause,
+    Action::MenuUp,
+    Action::MenuDown,
+    Action::MenuConfirm,
+    Action::MenuBack,
+];
+
+impl Action {
+    pub fn get_display_name(&self) -> &str {
+        match self {
+            Action::MoveUp => "Move Up",
+            Action::MoveDown => "Move Down",
+            Action::MoveLeft => "Move Left",
+            Action::MoveRight => "Move Right",
+            Action::Dash => "Dash",
+            Action::Ability1 => "Ability 1",
+            Action::Ability2 => "Ability 2",
+            Action::Fire => "Fire (Manual Mode)",
+            Action::ToggleFireMode => "Toggle Fire Mode",
+            Action::CycleAimSlot1 => "Cycle Aim: Slot 1",
+            Action::CycleAimSlot2 => "Cycle Aim: Slot 2",
+            Action::CycleAimSlot3 => "Cycle Aim: Slot 3",
+            Action::Pause => "Pause",
+            Action::MenuUp => "Menu Up",
+            Action::MenuDown => "Menu Down",
+            Action::MenuConfirm => "Menu Confirm",
+            Action::MenuBack => "Menu Back",
+        }
+    }
+
+    /// Name used in the controls config file.
+    pub fn get_config_name(&self) -> &str {
+        match self {
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::MoveLeft => "move_left",
+            Action::MoveRight => "move_right",
+            Action::Dash => "dash",
+            Action::Ability1 => "ability_1",
+            Action::Ability2 => "ability_2",
+            Action::Fire => "fire",
+            Action::ToggleFireMode => "toggle_fire_mode",
+            Action::CycleAimSlot1 => "cycle_aim_slot_1",
+            Action::CycleAimSlot2 => "cycle_aim_slot_2",
+            Action::CycleAimSlot3 => "cycle_aim_slot_3",
+            Action::Pause => "pause",
+            Action::MenuUp => "menu_up",
+            Action::MenuDown => "menu_down",
+            Action::MenuConfirm => "menu_confirm",
+            Action::MenuBack => "menu_back",
+        }
+    }
+
+    fn is_read_in_game(&self) -> bool {
+        !matches!(
+            self,
+            Action::MenuUp | Action::MenuDown | Action::MenuConfirm | Action::MenuBack
+        )
+    }
+
+    /// Moving up and down also navigates the menus, and pause closes them.
+    fn is_read_in_menus(&self) -> bool {
+        !self.is_read_in_game() || matches!(self, Action::MoveUp | Action::MoveDown | Action::Pause)
+    }
+
+    /// Whether both actions can be read at the same time, so they can't share a key.
+    fn shares_context(&self, other: &Action) -> bool {
+        (self.is_read_in_game() && other.is_read_in_game())
+            || (self.is_read_in_menus() && other.is_read_in_menus())
+    }
+
+    fn get_default_bindings(&self) -> Vec<Binding> {
+        use Binding::{Gamepad as Pad, Key};
+        match self {
+            Action::MoveUp => vec![Key(KeyboardKey::KEY_W)],
+            Action::MoveDown => vec![Key(KeyboardKey::KEY_S)],
+            Action::MoveLeft => vec![Key(KeyboardKey::KEY_A)],
+            Action::MoveRight => vec![Key(KeyboardKey::KEY_D)],
+            Action::Dash => vec![
+                Key(KeyboardKey::KEY_SPACE),
+                Pad(GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_DOWN),
+                Pad(GamepadButton::GAMEPAD_BUTTON_LEFT_TRIGGER_1),
+            ],
+            Action::Ability1 => vec![
+                Key(KeyboardKey::KEY_Q),
+                Pad(GamepadButton::GAMEPAD_BUTTON_RIGHT_TRIGGER_1),
+            ],
+            Action::Ability2 => vec![
+                Key(KeyboardKey::KEY_E),
+                Pad(GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_LEFT),
+            ],
+            Action::Fire => vec![
+                Binding::Mouse(MouseButton::MOUSE_BUTTON_LEFT),
+                Pad(GamepadButton::GAMEPAD_BUTTON_RIGHT_TRIGGER_2),
+            ],
+            Action::ToggleFireMode => vec![
+                Key(KeyboardKey::KEY_F),
+                Pad(GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_UP),
+            ],
+            Action::CycleAimSlot1 => vec![
+                Key(KeyboardKey::KEY_ONE),
+                Pad(GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_LEFT),
+            ],
+            Action::CycleAimSlot2 => vec![
+                Key(KeyboardKey::KEY_TWO),
+                Pad(GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_UP),
+            ],
+            Action::CycleAimSlot3 => vec![
+                Key(KeyboardKey::KEY_THREE),
+                Pad(GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_RIGHT),
+            ],
+            Action::Pause => vec![
+                Key(KeyboardKey::KEY_ESCAPE),
+                Key(KeyboardKey::KEY_P),
+                Pad(GamepadButton::GAMEPAD_BUTTON_MIDDLE_RIGHT),
+            ],
+            Action::MenuUp => vec![
+                Key(KeyboardKey::KEY_UP),
+                Pad(GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_UP),
+            ],
+            Action::MenuDown => vec![
+                Key(KeyboardKey::KEY_DOWN),
+                Pad(GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_DOWN),
+            ],
+            Action::MenuConfirm => vec![
+                Key(KeyboardKey::KEY_ENTER),
+                Key(KeyboardKey::KEY_SPACE),
+                Pad(GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_DOWN),
+            ],
+            Action::MenuBack => vec![
+                Key(KeyboardKey::KEY_BACKSPACE),
+                Pad(GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_RIGHT),
+            ],
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        ALL_ACTIONS
+            .iter()
+            .find(|action| action.get_config_name() == name)
+            .copied()
+            .ok_or_else(|| format!("unknown action `{}`", name))
+    }
+}
+
+// Config names for everything that can be bound
+const KEY_NAMES: [(&str, KeyboardKey); 60] = [
+    ("a", KeyboardKey::KEY_A),
+    ("b", KeyboardKey::KEY_B),
+    ("c", KeyboardKey::KEY_C),
+    ("d", KeyboardKey::KEY_D),
+    ("e", KeyboardKey::KEY_E),
+    ("f", KeyboardKey::KEY_F),
+    ("g", KeyboardKey::KEY_G),
+    ("h", KeyboardKey::KEY_H),
+    ("i", KeyboardKey::KEY_I),
+    ("j", KeyboardKey::KEY_J),
+    ("k", KeyboardKey::KEY_K),
+    ("l", KeyboardKey::KEY_L),
+    ("m", KeyboardKey::KEY_M),
+    ("n", KeyboardKey::KEY_N),
+    ("o", KeyboardKey::KEY_O),
+    ("p", KeyboardKey::KEY_P),
+    ("q", KeyboardKey::KEY_Q),
+    ("r", KeyboardKey::KEY_R),
+    ("s", KeyboardKey::KEY_S),
+    ("t", KeyboardKey::KEY_T),
+    ("u", KeyboardKey::KEY_U),
+    ("v", KeyboardKey::KEY_V),
+    ("w", KeyboardKey::KEY_W),
+    ("x", KeyboardKey::KEY_X),
+    ("y", KeyboardKey::KEY_Y),
+    ("z", KeyboardKey::KEY_Z),
+    ("0", KeyboardKey::KEY_ZERO),
+    ("1", KeyboardKey::KEY_ONE),
+    ("2", KeyboardKey::KEY_TWO),
+    ("3", KeyboardKey::KEY_THREE),
+    ("4", KeyboardKey::KEY_FOUR),
+    ("5", KeyboardKey::KEY_FIVE),
+    ("6", KeyboardKey::KEY_SIX),
+    ("7", KeyboardKey::KEY_SEVEN),
+    ("8", KeyboardKey::KEY_EIGHT),
+    ("9", KeyboardKey::KEY_NINE),
+    ("space", KeyboardKey::KEY_SPACE),
+    ("enter", KeyboardKey::KEY_ENTER),
+    ("escape", KeyboardKey::KEY_ESCAPE),
+    ("tab", KeyboardKey::KEY_TAB),
+    ("backspace", KeyboardKey::KEY_BACKSPACE),
+    ("up", KeyboardKey::KEY_UP),
+    ("down", KeyboardKey::KEY_DOWN),
+    ("left", KeyboardKey::KEY_LEFT),
+    ("right", KeyboardKey::KEY_RIGHT),
+    ("left_shift", KeyboardKey::KEY_LEFT_SHIFT),
+    ("right_shift", KeyboardKey::KEY_RIGHT_SHIFT),
+    ("left_control", KeyboardKey::KEY_LEFT_CONTROL),
+    ("right_control", KeyboardKey::KEY_RIGHT_CONTROL),
+    ("left_alt", KeyboardKey::KEY_LEFT_ALT),
+    ("right_alt", KeyboardKey::KEY_RIGHT_ALT),
+    ("caps_lock", KeyboardKey::KEY_CAPS_LOCK),
+    ("comma", KeyboardKey::KEY_COMMA),
+    ("period", KeyboardKey::KEY_PERIOD),
+    ("slash", KeyboardKey::KEY_SLASH),
+    ("semicolon", KeyboardKey::KEY_SEMICOLON),
+    ("apostrophe", KeyboardKey::KEY_APOSTROPHE),
+    ("minus", KeyboardKey::KEY_MINUS),
+    ("equal", KeyboardKey::KEY_EQUAL),
+    ("grave", KeyboardKey::KEY_GRAVE),
+];
+
+const MOUSE_NAMES: [(&str, MouseButton); 5] = [
+    ("mouse_left", MouseButton::MOUSE_BUTTON_LEFT),
+    ("mouse_right", MouseButton::MOUSE_BUTTON_RIGHT),
+    ("mouse_middle", MouseButton::MOUSE_BUTTON_MIDDLE),
+    ("mouse_side", MouseButton::MOUSE_BUTTON_SIDE),
+    ("mouse_extra", MouseButton::MOUSE_BUTTON_EXTRA),
+];
+
+const PAD_NAMES: [(&str, GamepadButton); 16] = [
+    ("pad_a", GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_DOWN),
+    ("pad_b", GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_RIGHT),
+    ("pad_x", GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_LEFT),
+    ("pad_y", GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_UP),
+    ("pad_lb", GamepadButton::GAMEPAD_BUTTON_LEFT_TRIGGER_1),
+    ("pad_lt", GamepadButton::GAMEPAD_BUTTON_LEFT_TRIGGER_2),
+    ("pad_rb", GamepadButton::GAMEPAD_BUTTON_RIGHT_TRIGGER_1),
+    ("pad_rt", GamepadButton::GAMEPAD_BUTTON_RIGHT_TRIGGER_2),
+    ("pad_up", GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_UP),
+    ("pad_down", GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_DOWN),
+    ("pad_left", GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_LEFT),
+    ("pad_right", GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_RIGHT),
+    ("pad_back", GamepadButton::GAMEPAD_BUTTON_MIDDLE_LEFT),
+    ("pad_start", GamepadButton::GAMEPAD_BUTTON_MIDDLE_RIGHT),
+    ("pad_l3", GamepadButton::GAMEPAD_BUTTON_LEFT_THUMB),
+    ("pad_r3", GamepadButton::GAMEPAD_BUTTON_RIGHT_THUMB),
+];
+
+fn find_name<T: PartialEq>(names: &[(&'static str, T)], value: &T) -> &'static str {
+    names
+        .iter()
+        .find(|(_, candidate)| candidate == value)
+        .map(|(name, _)| *name)
+        .unwrap_or("?")
+}
+
+fn find_value<T: Copy>(names: &[(&str, T)], name: &str) -> Option<T> {
+    names
+        .iter()
+        .find(|(known, _)| *known == name)
+        .map(|(_, value)| *value)
+}
+
+/// A single key, mouse button or gamepad button bound to an action.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Binding {
+    Key(KeyboardKey),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+}
+
+impl Binding {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Binding::Key(key) => find_name(&KEY_NAMES, key),
+            Binding::Mouse(button) => find_name(&MOUSE_NAMES, button),
+            Binding::Gamepad(button) => find_name(&PAD_NAMES, button),
+        }
+    }
+
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, Binding::Gamepad(_))
+    }
+
+    fn is_pressed(&self, rl: &RaylibHandle) -> bool {
+        match self {
+            Binding::Key(key) => rl.is_key_pressed(*key),
+            Binding::Mouse(button) => rl.is_mouse_button_pressed(*button),
+            Binding::Gamepad(button) => is_gamepad_button_pressed(rl, *button),
+        }
+    }
+
+    fn is_down(&self, rl: &RaylibHandle) -> bool {
+        match self {
+            Binding::Key(key) => rl.is_key_down(*key),
+            Binding::Mouse(button) => rl.is_mouse_button_down(*button),
+            Binding::Gamepad(button) => is_gamepad_button_down(rl, *button),
+        }
+    }
+
+    /// Whichever bindable key or button went down this frame, if any. Used by
+    /// the rebinding screen.
+    pub fn read_pressed(rl: &mut RaylibHandle) -> Option<Binding> {
+        if let Some(key) = rl.get_key_pressed() {
+            return KEY_NAMES
+                .iter()
+                .any(|(_, known)| *known == key)
+                .then_some(Binding::Key(key));
+        }
+        for (_, button) in MOUSE_NAMES {
+            if rl.is_mouse_button_pressed(button) {
+                return Some(Binding::Mouse(button));
+            }
+        }
+        PAD_NAMES
+            .iter()
+            .find(|(_, button)| is_gamepad_button_pressed(rl, *button))
+            .map(|(_, button)| Binding::Gamepad(*button))
+    }
+}
+
+impl FromStr for Binding {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        if let Some(key) = find_value(&KEY_NAMES, name) {
+            Ok(Binding::Key(key))
+        } else if let Some(button) = find_value(&MOUSE_NAMES, name) {
+            Ok(Binding::Mouse(button))
+        } else if let Some(button) = find_value(&PAD_NAMES, name) {
+            Ok(Binding::Gamepad(button))
+        } else {
+            Err(format!("unknown key or button `{}`", name))
+        }
+    }
+}
+
+/// Which keys and buttons trigger each action. Loaded from, and saved back
+/// to, the controls config file.
+pub struct InputMap {
+    bindings: HashMap<Action, Vec<Binding>>,
+}
+
+impl InputMap {
+    pub fn new() -> Self {
+        InputMap {
+            bindings: ALL_ACTIONS
+                .iter()
+                .map(|action| (*action, action.get_default_bindings()))
+                .collect(),
+        }
+    }
+
+    /// Like `load`, but a broken config falls back to the default bindings
+    /// instead of stopping the game. The error is handed back for the menus.
+    pub fn load_or_default(path: &str) -> (Self, Option<String>) {
+        match Self::load(path) {
+            Ok(map) => (map, None),
+            Err(error) => (Self::new(), Some(error)),
+        }
+    }
+
+    /// Falls back to the default bindings when there is no config file yet.
+    pub fn load(path: &str) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(error) => Err(format!("unable to read {}: {}", path, error)),
+        }
+    }
+
+    /// One `action = binding, binding` line per action. Actions left out of
+    /// the file keep their defaults.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut map = Self::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((action, bindings)) = line.split_once('=') else {
+                return Err(format!(
+                    "line {}: expected `action = bindings`",
+                    line_number
+                ));
+            };
+            let action: Action = action
+                .trim()
+                .parse()
+                .map_err(|error| format!("line {}: {}", line_number, error))?;
+            let bindings = bindings
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(Binding::from_str)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|error| format!("line {}: {}", line_number, error))?;
+            map.bindings.insert(action, bindings);
+        }
+        Ok(map)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.to_config_string())
+            .map_err(|error| format!("unable to write {}: {}", path, error))
+    }
+
+    pub fn to_config_string(&self) -> String {
+        let mut text = String::from(
+            "# Controls, rewritten whenever a binding is changed in the pause menu.\n\
+             # action = comma separated keys (w, space, left_shift), mouse buttons\n\
+             # (mouse_left) and gamepad buttons (pad_a, pad_rt, pad_start).\n",
+        );
+        for action in ALL_ACTIONS {
+            let names: Vec<&str> = self
+                .get_bindings(action)
+                .iter()
+                .map(|binding| binding.get_name())
+                .collect();
+            text.push_str(&format!(
+                "{} = {}\n",
+                action.get_config_name(),
+                names.join(", ")
+            ));
+        }
+        text
+    }
+
+    pub fn get_bindings(&self, action: Action) -> &[Binding] {
+        self.bindings
+            .get(&action)
+            .map(|bindings| bindings.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Keyboard/mouse binding shown in HUD hints, e.g. `SPACE`.
+    pub fn get_label(&self, action: Action) -> String {
+        self.get_bindings(action)
+            .iter()
+            .find(|binding| !binding.is_gamepad())
+            .map(|binding| binding.get_name().to_uppercase())
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    pub fn is_pressed(&self, rl: &RaylibHandle, action: Action) -> bool {
+        self.get_bindings(action)
+            .iter()
+            .any(|binding| binding.is_pressed(rl))
+    }
+
+    pub fn is_down(&self, rl: &RaylibHandle, action: Action) -> bool {
+        self.get_bindings(action)
+            .iter()
+            .any(|binding| binding.is_down(rl))
+    }
+
+    /// Which other action already uses `binding` where `action` is read, if any.
+    pub fn find_conflict(&self, action: Action, binding: Binding) -> Option<Action> {
+        ALL_ACTIONS.into_iter().find(|other| {
+            *other != action
+                && other.shares_context(&action)
+                && self.get_bindings(*other).contains(&binding)
+        })
+    }
+
+    /// Replaces the action's keyboard/mouse bindings, or its gamepad bindings,
+    /// depending on what `binding` is. The other device keeps its bindings.
+    /// Refused if another action read at the same time already uses it.
+    pub fn rebind(&mut self, action: Action, binding: Binding) -> Result<(), String> {
+        if let Some(other) = self.find_conflict(action, binding) {
+            return Err(format!(
+                "{} is already bound to {}",
+                binding.get_name().to_uppercase(),
+                other.get_display_name()
+            ));
+        }
+        let bindings = self.bindings.entry(action).or_default();
+        bindings.retain(|existing| existing.is_gamepad() != binding.is_gamepad());
+        bindings.push(binding);
+        Ok(())
+    }
+
+    pub fn reset_to_defaults(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadzone_rescales_stick_travel() {
+        assert_eq!(apply_deadzone(Vector2::new(0.1, 0.1)), Vector2::zero());
+
+        let full = apply_deadzone(Vector2::new(0.0, -1.0));
+        assert!((full.y + 1.0).abs() < 0.001);
+
+        let half = apply_deadzone(Vector2::new(0.6, 0.0));
+        assert!((half.x - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_config_round_trips() {
+        let mut map = InputMap::new();
+        map.rebind(Action::Dash, Binding::Key(KeyboardKey::KEY_LEFT_SHIFT))
+            .unwrap();
+
+        let loaded = InputMap::parse(&map.to_config_string()).unwrap();
+        for action in ALL_ACTIONS {
+            assert_eq!(loaded.get_bindings(action), map.get_bindings(action));
+        }
+    }
+
+    #[test]
+    fn test_rebind_keeps_other_device() {
+        let mut map = InputMap::new();
+        map.rebind(
+            Action::Dash,
+            Binding::Mouse(MouseButton::MOUSE_BUTTON_RIGHT),
+        )
+        .unwrap();
+
+        assert_eq!(
+            map.get_bindings(Action::Dash),
+            [
+                Binding::Gamepad(GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_DOWN),
+                Binding::Gamepad(GamepadButton::GAMEPAD_BUTTON_LEFT_TRIGGER_1),
+                Binding::Mouse(MouseButton::MOUSE_BUTTON_RIGHT),
+            ]
+        );
+        assert_eq!(map.get_label(Action::Dash), "MOUSE_RIGHT");
+    }
+
+    #[test]
+    fn test_rebind_rejects_keys_already_in_use() {
+        let mut map = InputMap::new();
+        for action in ALL_ACTIONS {
+            for binding in map.get_bindings(action) {
+                assert_eq!(map.find_conflict(action, *binding), None, "{:?}", action);
+            }
+        }
+
+        let error = map
+            .rebind(Action::Ability1, Binding::Key(KeyboardKey::KEY_SPACE))
+            .unwrap_err();
+        assert_eq!(error, "SPACE is already bound to Dash");
+        assert_eq!(
+            map.get_label(Action::Ability1),
+            "Q",
+            "Bindings are untouched"
+        );
+
+        // Menus and gameplay are never read together, so they can share keys
+        map.rebind(Action::MenuBack, Binding::Key(KeyboardKey::KEY_Q))
+            .unwrap();
+        // But movement also navigates the menus
+        assert!(map
+            .rebind(Action::MenuConfirm, Binding::Key(KeyboardKey::KEY_W))
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_the_line() {
+        let error = InputMap::parse("dash = space\nfire = laser").err().unwrap();
+        assert!(error.starts_with("line 2"), "{}", error);
+
+        // Missing actions keep their defaults
+        let map = InputMap::parse("dash = q").unwrap();
+        assert_eq!(map.get_label(Action::MoveUp), "W");
+    }
+}
@@ -1,9 +1,16 @@
+pub mod abilities;
+pub mod classes;
+pub mod difficulty;
+pub mod drops;
 pub mod enemy;
 pub mod game_state;
+pub mod input;
+pub mod menu;
 pub mod player;
 pub mod projectiles;
 pub mod renderer;
 pub mod statuses;
+pub mod upgrades;
 pub mod utils;
 pub mod weapon_pickups;
 pub mod weapons;
@@ -1,7 +1,13 @@
 use warhammer_rougelite::{
+    enemy::{
+        director::{DirectorScript, DIRECTOR_SCRIPT_PATH},
+        AllEnemies, EnemyTextures,
+    },
     game_state,
+    input::{InputMap, CONTROLS_CONFIG_PATH},
+    menu::{read_menu_input, ClassSelect},
     player::{self},
-    renderer::render_game_state,
+    renderer::{render_class_select, render_game_state},
     utils::Position,
 };
 
@@ -15,6 +21,8 @@ fn main() {
         .build();
 
     rl.hide_cursor();
+    // Escape opens the pause menu rather than closing the window
+    rl.set_exit_key(None);
 
     // TODO split game integration loop from FPS so I don't need this.
     //rl.set_target_fps(144);
@@ -27,6 +35,18 @@ fn main() {
         .load_texture(&thread, "./assests/sprites/servo-skull.png")
         .unwrap();
 
+    let dark_fighter_texture = rl
+        .load_texture(&thread, "./assests/sprites/dark-fighter.png")
+        .unwrap();
+
+    let dark_tanker_texture = rl
+        .load_texture(&thread, "./assests/sprites/dark-tanker.png")
+        .unwrap();
+
+    let crescent_moon_texture = rl
+        .load_texture(&thread, "./assests/sprites/crescent-moon-alien.png")
+        .unwrap();
+
     let bullet_texture = rl
         .load_texture(&thread, "./assests/sprites/bullet_new.png")
         .unwrap();
@@ -39,23 +59,59 @@ fn main() {
         .load_texture(&thread, "./assests/sprites/ground-tile-02.png")
         .unwrap();
 
+    let director_script = DirectorScript::load(DIRECTOR_SCRIPT_PATH)
+        .unwrap_or_else(|error| panic!("Spawn director script failed to load: {}", error));
+
+    // controls.txt is hand editable, so a broken one shouldn't stop the game
+    let (input_map, controls_error) = InputMap::load_or_default(CONTROLS_CONFIG_PATH);
+
+    // Character and difficulty select run before anything else
+    let mut class_select = ClassSelect::new();
+    let (class, difficulty) = loop {
+        if rl.window_should_close() {
+            return;
+        }
+        let confirmed =
+            read_menu_input(&rl, &input_map).and_then(|input| class_select.handle_input(input));
+        if let Some(choice) = confirmed {
+            break choice;
+        }
+        render_class_select(&mut rl, &thread, &class_select, &player_texture, &input_map);
+    };
+
     let position = Position {
         x: (rl.get_screen_width() / 2) as f32,
         y: (rl.get_screen_height() / 2) as f32,
     };
-    let player = player::Player::new(position, player_texture);
+    let player = player::Player::new(position, player_texture)
+        .with_class(class)
+        .with_difficulty(difficulty);
+
+    let enemies = AllEnemies::new(
+        EnemyTextures {
+            servo_skull: &enemy_texture,
+            dark_fighter: &dark_fighter_texture,
+            dark_tanker: &dark_tanker_texture,
+            crescent_moon: &crescent_moon_texture,
+        },
+        director_script,
+    );
 
     let mut game_state = game_state::GameState::new(
         &mut rl,
         &thread,
         player,
-        &enemy_texture,
+        enemies,
         &bullet_texture,
         &ground_texture,
         &ground_texture2,
-    );
+    )
+    .with_input_map(input_map, controls_error);
 
-    while !game_state.rl.window_should_close() && game_state.player_alive() {
+    while !game_state.rl.window_should_close()
+        && game_state.player_alive()
+        && game_state.is_running()
+    {
         let delta = game_state.rl.get_frame_time(); // only get the delta a single time.
         game_state.game_tick(&delta);
         render_game_state(&mut game_state, &thread);
@@ -0,0 +1,371 @@
+use raylib::ffi::KeyboardKey;
+use raylib::prelude::*;
+
+use crate::classes::{PlayerClass, ALL_CLASSES};
+use crate::difficulty::{Difficulty, ALL_DIFFICULTIES};
+use crate::input::{Action, Binding, InputMap, ALL_ACTIONS, CONTROLS_CONFIG_PATH};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MenuInput {
+    Up,
+    Down,
+    Confirm,
+    Back,
+}
+
+/// Menu navigation from the menu actions. The move up/down bindings navigate
+/// too, so rebound movement carries over to the menus.
+pub fn read_menu_input(rl: &RaylibHandle, input_map: &InputMap) -> Option<MenuInput> {
+    let pressed = |actions: &[Action]| {
+        actions
+            .iter()
+            .any(|action| input_map.is_pressed(rl, *action))
+    };
+
+    if pressed(&[Action::MenuUp, Action::MoveUp]) {
+        Some(MenuInput::Up)
+    } else if pressed(&[Action::MenuDown, Action::MoveDown]) {
+        Some(MenuInput::Down)
+    } else if pressed(&[Action::MenuConfirm]) {
+        Some(MenuInput::Confirm)
+    } else if pressed(&[Action::MenuBack]) {
+        Some(MenuInput::Back)
+    } else {
+        None
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PauseOption {
+    Resume,
+    Controls,
+    Quit,
+}
+
+pub const PAUSE_OPTIONS: [PauseOption; 3] = [
+    PauseOption::Resume,
+    PauseOption::Controls,
+    PauseOption::Quit,
+];
+
+impl PauseOption {
+    pub fn get_display_name(&self) -> &str {
+        match self {
+            PauseOption::Resume => "Resume",
+            PauseOption::Controls => "Controls",
+            PauseOption::Quit => "Quit",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ControlsRow {
+    Action(Action),
+    ResetDefaults,
+    Back,
+}
+
+/// Every action, followed by the reset and back buttons.
+pub fn get_controls_rows() -> Vec<ControlsRow> {
+    ALL_ACTIONS
+        .iter()
+        .map(|action| ControlsRow::Action(*action))
+        .chain([ControlsRow::ResetDefaults, ControlsRow::Back])
+        .collect()
+}
+
+/// The rebinding screen, opened from the pause menu.
+pub struct ControlsScreen {
+    pub selected: usize,
+    /// Set while waiting for the new key or button for the selected action.
+    pub waiting_for_binding: bool,
+    /// Shown when the bindings couldn't be loaded or saved, or a rebind clashed.
+    pub error: Option<String>,
+}
+
+impl ControlsScreen {
+    fn new(error: Option<String>) -> Self {
+        ControlsScreen {
+            selected: 0,
+            waiting_for_binding: false,
+            error,
+        }
+    }
+
+    pub fn get_selected(&self) -> ControlsRow {
+        get_controls_rows()[self.selected]
+    }
+}
+
+/// The menu shown over the game while it's paused.
+pub struct PauseMenu {
+    pub open: bool,
+    pub selected: usize,
+    pub quit_requested: bool,
+    pub controls: Option<ControlsScreen>,
+    /// Last problem reading or writing the controls config, kept until a save works.
+    pub config_error: Option<String>,
+}
+
+impl PauseMenu {
+    pub fn new() -> Self {
+        PauseMenu {
+            open: false,
+            selected: 0,
+            quit_requested: false,
+            controls: None,
+            config_error: None,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.selected = 0;
+        self.controls = None;
+    }
+
+    /// Runs the menu for a frame. Bindings are saved as soon as they change.
+    pub fn update(&mut self, rl: &mut RaylibHandle, input_map: &mut InputMap) {
+        let changed = if self
+            .controls
+            .as_ref()
+            .is_some_and(|controls| controls.waiting_for_binding)
+        {
+            // The next key or button becomes the binding, even the pause key.
+            // Escape backs out instead
+            self.capture_binding(rl, input_map)
+        } else if input_map.is_pressed(rl, Action::Pause) {
+            if self.controls.take().is_none() {
+                self.toggle();
+            }
+            false
+        } else if let Some(input) = read_menu_input(rl, input_map) {
+            self.handle_input(input, input_map)
+        } else {
+            false
+        };
+
+        if changed {
+            self.config_error = input_map.save(CONTROLS_CONFIG_PATH).err();
+            if let Some(controls) = self.controls.as_mut() {
+                controls.error = self.config_error.clone();
+            }
+        }
+    }
+
+    fn capture_binding(&mut self, rl: &mut RaylibHandle, input_map: &mut InputMap) -> bool {
+        let Some(controls) = self.controls.as_mut() else {
+            return false;
+        };
+        if rl.is_key_pressed(KeyboardKey::KEY_ESCAPE) {
+            controls.waiting_for_binding = false;
+            return false;
+        }
+        let Some(binding) = Binding::read_pressed(rl) else {
+            return false;
+        };
+        controls.waiting_for_binding = false;
+        match controls.get_selected() {
+            ControlsRow::Action(action) => match input_map.rebind(action, binding) {
+                Ok(()) => true,
+                Err(error) => {
+                    controls.error = Some(error);
+                    false
+                }
+            },
+            ControlsRow::ResetDefaults | ControlsRow::Back => false,
+        }
+    }
+
+    pub fn get_selected(&self) -> PauseOption {
+        PAUSE_OPTIONS[self.selected]
+    }
+
+    /// Returns true when the bindings were changed.
+    pub fn handle_input(&mut self, input: MenuInput, input_map: &mut InputMap) -> bool {
+        if let Some(controls) = self.controls.as_mut() {
+            let count = get_controls_rows().len();
+            match input {
+                MenuInput::Up => controls.selected = (controls.selected + count - 1) % count,
+                MenuInput::Down => controls.selected = (controls.selected + 1) % count,
+                MenuInput::Back => self.controls = None,
+                MenuInput::Confirm => match controls.get_selected() {
+                    ControlsRow::Action(_) => controls.waiting_for_binding = true,
+                    ControlsRow::ResetDefaults => {
+                        input_map.reset_to_defaults();
+                        return true;
+                    }
+                    ControlsRow::Back => self.controls = None,
+                },
+            }
+            return false;
+        }
+
+        let count = PAUSE_OPTIONS.len();
+        match input {
+            MenuInput::Up => self.selected = (self.selected + count - 1) % count,
+            MenuInput::Down => self.selected = (self.selected + 1) % count,
+            MenuInput::Back => self.open = false,
+            MenuInput::Confirm => match self.get_selected() {
+                PauseOption::Resume => self.open = false,
+                PauseOption::Controls => {
+                    self.controls = Some(ControlsScreen::new(self.config_error.clone()))
+                }
+                PauseOption::Quit => self.quit_requested = true,
+            },
+        }
+        false
+    }
+}
+
+impl Default for PauseMenu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Character select, shown before the run starts. Confirming a class moves
+/// on to picking the difficulty.
+pub struct ClassSelect {
+    pub selected: usize,
+    /// Set once a class is picked, while the difficulty is being chosen.
+    pub choosing_difficulty: bool,
+    pub difficulty_selected: usize,
+}
+
+impl ClassSelect {
+    pub fn new() -> Self {
+        let normal = ALL_DIFFICULTIES
+            .iter()
+            .position(|difficulty| *difficulty == Difficulty::default())
+            .unwrap_or(0);
+        ClassSelect {
+            selected: 0,
+            choosing_difficulty: false,
+            difficulty_selected: normal,
+        }
+    }
+
+    pub fn get_selected(&self) -> PlayerClass {
+        ALL_CLASSES[self.selected]
+    }
+
+    pub fn get_selected_difficulty(&self) -> Difficulty {
+        ALL_DIFFICULTIES[self.difficulty_selected]
+    }
+
+    /// Returns the class and difficulty once both have been confirmed.
+    pub fn handle_input(&mut self, input: MenuInput) -> Option<(PlayerClass, Difficulty)> {
+        if self.choosing_difficulty {
+            let count = ALL_DIFFICULTIES.len();
+            let selected = &mut self.difficulty_selected;
+            match input {
+                MenuInput::Up => *selected = (*selected + count - 1) % count,
+                MenuInput::Down => *selected = (*selected + 1) % count,
+                MenuInput::Confirm => {
+                    return Some((self.get_selected(), self.get_selected_difficulty()))
+                }
+                MenuInput::Back => self.choosing_difficulty = false,
+            }
+            return None;
+        }
+
+        let count = ALL_CLASSES.len();
+        match input {
+            MenuInput::Up => self.selected = (self.selected + count - 1) % count,
+            MenuInput::Down => self.selected = (self.selected + 1) % count,
+            MenuInput::Confirm => self.choosing_difficulty = true,
+            MenuInput::Back => {}
+        }
+        None
+    }
+}
+
+impl Default for ClassSelect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_menu_navigation_wraps() {
+        let mut input_map = InputMap::new();
+        let mut menu = PauseMenu::new();
+        menu.toggle();
+
+        menu.handle_input(MenuInput::Up, &mut input_map);
+        assert_eq!(menu.get_selected(), PauseOption::Quit);
+        menu.handle_input(MenuInput::Down, &mut input_map);
+        assert_eq!(menu.get_selected(), PauseOption::Resume);
+
+        menu.handle_input(MenuInput::Confirm, &mut input_map);
+        assert!(!menu.open, "Resume closes the menu");
+        assert!(!menu.quit_requested);
+    }
+
+    #[test]
+    fn test_controls_screen_waits_for_binding_and_resets() {
+        let mut input_map = InputMap::new();
+        input_map
+            .rebind(Action::Dash, Binding::Key(KeyboardKey::KEY_R))
+            .unwrap();
+        let mut menu = PauseMenu::new();
+        menu.toggle();
+
+        menu.handle_input(MenuInput::Down, &mut input_map);
+        menu.handle_input(MenuInput::Confirm, &mut input_map);
+        let controls = menu.controls.as_ref().expect("Controls screen should open");
+        assert_eq!(controls.get_selected(), ControlsRow::Action(Action::MoveUp));
+
+        menu.handle_input(MenuInput::Confirm, &mut input_map);
+        assert!(menu.controls.as_ref().unwrap().waiting_for_binding);
+
+        // Up from the first row wraps round to Back, one more Up is Reset Defaults
+        menu.controls.as_mut().unwrap().waiting_for_binding = false;
+        menu.handle_input(MenuInput::Up, &mut input_map);
+        menu.handle_input(MenuInput::Up, &mut input_map);
+        assert!(menu.handle_input(MenuInput::Confirm, &mut input_map));
+        assert_eq!(input_map.get_label(Action::Dash), "SPACE");
+
+        menu.handle_input(MenuInput::Back, &mut input_map);
+        assert!(menu.controls.is_none());
+        assert!(menu.open, "Back only leaves the controls screen");
+    }
+
+    #[test]
+    fn test_class_select_picks_class_then_difficulty() {
+        let mut select = ClassSelect::new();
+        select.handle_input(MenuInput::Down);
+        assert_eq!(select.handle_input(MenuInput::Confirm), None);
+        assert!(select.choosing_difficulty);
+        assert_eq!(select.get_selected_difficulty(), Difficulty::Normal);
+
+        // Back goes to the class list without losing the pick
+        select.handle_input(MenuInput::Back);
+        assert!(!select.choosing_difficulty);
+        select.handle_input(MenuInput::Confirm);
+
+        select.handle_input(MenuInput::Down);
+        assert_eq!(
+            select.handle_input(MenuInput::Confirm),
+            Some((ALL_CLASSES[1], Difficulty::Hard))
+        );
+    }
+
+    #[test]
+    fn test_config_error_shows_on_controls_screen() {
+        let mut input_map = InputMap::new();
+        let mut menu = PauseMenu::new();
+        menu.config_error = Some("line 3: unknown action `jump`".to_string());
+        menu.toggle();
+
+        menu.handle_input(MenuInput::Down, &mut input_map);
+        menu.handle_input(MenuInput::Confirm, &mut input_map);
+        let controls = menu.controls.as_ref().expect("Controls screen should open");
+        assert_eq!(controls.error.as_deref(), menu.config_error.as_deref());
+    }
+}
@@ -1,24 +1,54 @@
 use crate::{
-    projectiles::{
-        bolter::BolterProjectile, multi_melta::MultiMeltaProjectile,
-        power_sword::PowerSwordProjectile, shotgun::ShotgunProjectile, Projectile,
-    },
+    abilities::Ability,
+    classes::PlayerClass,
+    difficulty::Difficulty,
+    enemy::AllEnemies,
+    input::{self, Action, InputDevice, InputMap, GAMEPAD_AIM_DISTANCE},
+    projectiles::{presets::HazardKind, Projectile},
     statuses::*,
-    weapons::{Weapon, WeaponData},
+    weapons::{AimMode, Weapon, WeaponData},
 };
 
 use crate::utils::{Direction, Position};
-use raylib::ffi::KeyboardKey;
 use raylib::prelude::*;
 use std::f32::consts::PI;
 
 // Scale factor for player sprite rendering (higher value = smaller sprite)
 pub const PLAYER_SCALE: f32 = 1.5;
 const STACKED_SHOT_DELAY: f32 = 0.06;
+const GRENADE_THROW_DISTANCE: f32 = 300.0;
+const NEAREST_ENEMY_AIM_RANGE: f32 = 900.0;
+// Movement input shorter than this is treated as standing still
+const MOVEMENT_DEADZONE: f32 = 0.15;
+// Drops and pickups closer than this start flying toward the player
+const MAGNET_RADIUS: f32 = 150.0;
+
+// Dash tuning
+const DASH_SPEED: f32 = 1400.0;
+const DASH_DURATION: f32 = 0.18;
+const DASH_RECHARGE_TIME: f32 = 1.5; // Per charge
+const DASH_INVULNERABILITY: f32 = 0.25; // Slightly longer than the dash itself
+pub const MAX_DASH_CHARGES: i32 = 3;
+
+// Shield tuning
+const SHIELD_RECHARGE_DELAY: f32 = 3.0; // Seconds without a hit before it refills
+const SHIELD_RECHARGE_RATE: f32 = 10.0; // Per second
+const MIN_ARMORED_DAMAGE: i32 = 1;
+
+// Last stand and revive tuning
+const LAST_STAND_DURATION: f32 = 5.0;
+const LAST_STAND_DAMAGE_MULTIPLIER: f32 = 1.5;
+const REVIVE_HEALTH_FRACTION: f32 = 0.5;
+const REVIVE_INVULNERABILITY: f32 = 2.0;
+pub const REVIVE_SHOCKWAVE_RADIUS: f32 = 350.0;
 
 pub struct MouseInformation(f32);
 
 impl MouseInformation {
+    pub fn get_angle(&self) -> f32 {
+        self.0
+    }
+
     pub fn get_angle_degrees(&self) -> f32 {
         self.0.to_degrees()
     }
@@ -33,22 +63,120 @@ impl MouseInformation {
     }
 }
 
+/// Charges for the Space dash. Charges refill one at a time.
+pub struct Dash {
+    pub charges: i32,
+    pub max_charges: i32,
+    pub recharge_remaining: f32,
+    /// Set while a dash is moving the player.
+    pub active_remaining: f32,
+    pub angle: f32,
+    /// How long each dash lasts, longer with a jump pack.
+    pub duration: f32,
+}
+
+impl Dash {
+    fn new() -> Self {
+        Dash {
+            charges: 1,
+            max_charges: 1,
+            recharge_remaining: 0.0,
+            active_remaining: 0.0,
+            angle: 0.0,
+            duration: DASH_DURATION,
+        }
+    }
+
+    pub fn is_dashing(&self) -> bool {
+        self.active_remaining > 0.0
+    }
+
+    /// 0.0 right after a charge is spent, 1.0 when the next one is ready.
+    pub fn get_recharge_progress(&self) -> f32 {
+        1.0 - (self.recharge_remaining / DASH_RECHARGE_TIME).clamp(0.0, 1.0)
+    }
+
+    fn tick(&mut self, delta: f32) {
+        self.active_remaining = (self.active_remaining - delta).max(0.0);
+        if self.charges >= self.max_charges {
+            return;
+        }
+        self.recharge_remaining -= delta;
+        if self.recharge_remaining <= 0.0 {
+            self.charges += 1;
+            self.recharge_remaining = DASH_RECHARGE_TIME;
+        }
+    }
+
+    fn start(&mut self, angle: f32) {
+        if self.charges == self.max_charges {
+            self.recharge_remaining = DASH_RECHARGE_TIME;
+        }
+        self.charges -= 1;
+        self.active_remaining = self.duration;
+        self.angle = angle;
+    }
+}
+
 pub struct Player {
+    pub class: PlayerClass,
     pub position: Position,
     /// derived from the mouse aiming
     pub aiming_direction: Direction,
     /// derived from the player moving.
     pub moving_direction: Direction,
+    /// Full 360° angle of the last movement input, in radians.
+    pub facing_angle: f32,
+    /// This frame's movement input, never longer than 1.0.
+    pub movement: Vector2,
 
     pub mouse_info: MouseInformation,
+    /// How far from the player the crosshair is, used by targeted abilities.
+    pub aim_distance: f32,
+    /// Switches to the gamepad when the right stick aims, and back when the mouse moves.
+    pub input_device: InputDevice,
 
     pub move_speed: f32,
     pub health: i32,
     pub max_health: i32,
+    /// Flat reduction on every hit, never below 1 damage.
+    pub armor: i32,
+    /// Soaks damage before health, refilling after a while without being hit.
+    pub shield: f32,
+    pub max_shield: f32,
+    pub time_since_hit: f32,
 
     // game mechanic data
     pub statuses: Vec<Status>,
     pub weapons: [Option<Weapon>; 3],
+    /// Triggered by hand, at most two.
+    pub abilities: Vec<Ability>,
+    /// Extra bounces granted to ballistic rounds by Ricochet Rounds upgrades.
+    pub ricochet_bounces: i32,
+    /// When set, weapons only fire while the left mouse button is held.
+    pub manual_fire: bool,
+    pub trigger_held: bool,
+    /// Multiplier on how fast magazines refill, raised by upgrades.
+    pub reload_speed: f32,
+    pub dash: Dash,
+    /// Damage is ignored while this is above zero.
+    pub invulnerable_remaining: f32,
+    /// I-frames granted after every hit, set by the difficulty.
+    pub hit_invulnerability: f32,
+    /// Scales incoming damage, lowered by the Devastator's passive.
+    pub damage_taken_multiplier: f32,
+    /// Emperor's Blessings held, each one brings the player back from death once.
+    pub blessings: i32,
+    /// Whether a lethal hit drops the player into a last stand, set by the difficulty.
+    pub last_stand_enabled: bool,
+    /// The last stand only happens once per life.
+    pub last_stand_used: bool,
+    /// Seconds left fighting at 1 HP before the player falls.
+    pub last_stand_remaining: f32,
+    /// Drops and pickups inside this radius are pulled toward the player.
+    pub magnet_radius: f32,
+    /// While above zero, everything on the map is pulled in.
+    pub vacuum_remaining: f32,
 
     // Rendering bits
     pub texture: Texture2D,
@@ -61,111 +189,192 @@ impl<'a> Player {
         let collision_radius = (texture.width as f32 / PLAYER_SCALE) / 2.0;
 
         Player {
+            class: PlayerClass::TacticalMarine,
             position,
             aiming_direction: Direction::Right,
             moving_direction: Direction::Right,
+            facing_angle: 0.0,
+            movement: Vector2::zero(),
             mouse_info: MouseInformation(0.0),
+            aim_distance: GAMEPAD_AIM_DISTANCE,
+            input_device: InputDevice::KeyboardMouse,
             move_speed: 300.0,
             health: 100,
             max_health: 100,
+            armor: 0,
+            shield: 0.0,
+            max_shield: 0.0,
+            time_since_hit: 0.0,
             statuses: vec![],
-            weapons: [
-                Some(Weapon::Bolter(WeaponData {
-                    damage: 10.0,
-                    tick_interval: 1.0,
-                    time_since_last_tick: 0.0,
-                    stack_count: 1,
-                    queued_shots: vec![],
-                })),
-                None,
-                None,
-            ],
+            weapons: [Some(Weapon::new_bolter()), None, None],
+            abilities: PlayerClass::TacticalMarine
+                .get_abilities()
+                .into_iter()
+                .map(Ability::new)
+                .collect(),
+            ricochet_bounces: 0,
+            manual_fire: false,
+            trigger_held: false,
+            reload_speed: 1.0,
+            dash: Dash::new(),
+            invulnerable_remaining: 0.0,
+            hit_invulnerability: Difficulty::default().get_hit_invulnerability(),
+            damage_taken_multiplier: 1.0,
+            blessings: Difficulty::default().get_starting_blessings(),
+            last_stand_enabled: Difficulty::default().allows_last_stand(),
+            last_stand_used: false,
+            last_stand_remaining: 0.0,
+            magnet_radius: MAGNET_RADIUS,
+            vacuum_remaining: 0.0,
             texture,
             collision_radius,
         }
     }
 
+    pub fn with_difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.hit_invulnerability = difficulty.get_hit_invulnerability();
+        self.blessings = difficulty.get_starting_blessings();
+        self.last_stand_enabled = difficulty.allows_last_stand();
+        self
+    }
+
+    /// Swaps in the class's stats, starting weapon and passive.
+    pub fn with_class(mut self, class: PlayerClass) -> Self {
+        self.class = class;
+        self.max_health = class.get_max_health();
+        self.health = self.max_health;
+        self.armor = class.get_armor();
+        self.max_shield = class.get_max_shield();
+        self.shield = self.max_shield;
+        self.move_speed = class.get_move_speed();
+        self.weapons = [Some(class.get_starting_weapon()), None, None];
+        self.abilities = class
+            .get_abilities()
+            .into_iter()
+            .map(Ability::new)
+            .collect();
+        self.reload_speed = class.get_reload_speed();
+        self.damage_taken_multiplier = class.get_damage_taken_multiplier();
+        self.dash.max_charges = class.get_dash_charges();
+        self.dash.charges = self.dash.max_charges;
+        if let Some(duration) = class.get_dash_duration() {
+            self.dash.duration = duration;
+        }
+        self
+    }
+
     pub fn update_aim_direction(
         &mut self,
         rl: &raylib::RaylibHandle,
         camera: raylib::ffi::Camera2D,
     ) {
-        let mouse_screen = rl.get_mouse_position();
-        let mouse_world = rl.get_screen_to_world2D(mouse_screen, camera);
+        // The right stick aims on its own, the last direction is kept once it's let go
+        let stick = input::get_right_stick(rl);
+        if stick != Vector2::zero() {
+            self.input_device = InputDevice::Gamepad;
+            self.mouse_info = MouseInformation(stick.y.atan2(stick.x));
+            self.aim_distance = GAMEPAD_AIM_DISTANCE;
+        } else if rl.get_mouse_delta() != Vector2::zero() {
+            self.input_device = InputDevice::KeyboardMouse;
+        }
 
-        let dx = mouse_world.x - self.position.x;
-        let dy = mouse_world.y - self.position.y;
+        if self.input_device == InputDevice::KeyboardMouse {
+            let mouse_screen = rl.get_mouse_position();
+            let mouse_world = rl.get_screen_to_world2D(mouse_screen, camera);
 
-        // Calculate angle in radians (atan2 returns -PI to PI)
-        let angle = dy.atan2(dx);
-        self.mouse_info = MouseInformation(angle);
+            let dx = mouse_world.x - self.position.x;
+            let dy = mouse_world.y - self.position.y;
+
+            // Calculate angle in radians (atan2 returns -PI to PI)
+            let angle = dy.atan2(dx);
+            self.mouse_info = MouseInformation(angle);
+            self.aim_distance = (dx * dx + dy * dy).sqrt();
+        }
         self.aiming_direction = self.mouse_info.get_direction();
     }
 
-    pub fn handle_user_input(&mut self, rl: &raylib::RaylibHandle, delta: &f32) {
-        let speed_multiplier = self.calculate_speed_multiplier();
-        let effective_speed = self.move_speed * speed_multiplier;
+    pub fn handle_user_input(
+        &mut self,
+        rl: &raylib::RaylibHandle,
+        input_map: &InputMap,
+        delta: &f32,
+    ) {
+        // Movement only, direction is handled by the mouse or right stick
+        let mut input = Vector2::zero();
+        if input_map.is_down(rl, Action::MoveUp) {
+            input.y -= 1.0;
+        }
+        if input_map.is_down(rl, Action::MoveDown) {
+            input.y += 1.0;
+        }
+        if input_map.is_down(rl, Action::MoveLeft) {
+            input.x -= 1.0;
+        }
+        if input_map.is_down(rl, Action::MoveRight) {
+            input.x += 1.0;
+        }
+        // The left stick adds on top, apply_movement clamps the total
+        input += input::get_left_stick(rl);
+        self.apply_movement(input, *delta);
+
+        // Dash in the input direction, or the facing direction when standing still
+        self.dash.tick(*delta);
+        self.invulnerable_remaining = (self.invulnerable_remaining - delta).max(0.0);
+        if input_map.is_pressed(rl, Action::Dash) && self.can_dash() {
+            self.dash.start(self.facing_angle);
+            let invulnerability = DASH_INVULNERABILITY.max(self.dash.duration);
+            self.invulnerable_remaining = self.invulnerable_remaining.max(invulnerability);
+        }
+        if self.dash.is_dashing() {
+            self.position.x += self.dash.angle.cos() * DASH_SPEED * delta;
+            self.position.y += self.dash.angle.sin() * DASH_SPEED * delta;
+        }
 
-        // Handle WASD input (movement only, direction is handled by mouse)
-        if rl.is_key_down(KeyboardKey::KEY_W) {
-            self.moving_direction = Direction::Up;
-            self.position.y -= effective_speed * delta;
+        if input_map.is_pressed(rl, Action::ToggleFireMode) {
+            self.manual_fire = !self.manual_fire;
         }
-        if rl.is_key_down(KeyboardKey::KEY_S) {
-            self.moving_direction = Direction::Down;
-            self.position.y += effective_speed * delta;
+        self.trigger_held = input_map.is_down(rl, Action::Fire);
+
+        // Cycle the aim mode of that weapon slot
+        let slot_actions = [
+            Action::CycleAimSlot1,
+            Action::CycleAimSlot2,
+            Action::CycleAimSlot3,
+        ];
+        for (slot, action) in self.weapons.iter_mut().zip(slot_actions) {
+            let Some(weapon) = slot else { continue };
+            if weapon.is_aimed() && input_map.is_pressed(rl, action) {
+                weapon.cycle_aim_mode();
+            }
         }
-        if rl.is_key_down(KeyboardKey::KEY_A) {
-            self.moving_direction = Direction::Left;
-            self.position.x -= effective_speed * delta;
+    }
+
+    /// Walks the player along `input`. Diagonals are clamped to the same top
+    /// speed as straight lines, while shorter (analog) input walks slower.
+    /// Also updates the facing angle and direction the weapons aim along.
+    pub fn apply_movement(&mut self, input: Vector2, delta: f32) {
+        let length = input.length();
+        if length < MOVEMENT_DEADZONE {
+            self.movement = Vector2::zero();
+            return;
         }
-        if rl.is_key_down(KeyboardKey::KEY_D) {
-            self.moving_direction = Direction::Right;
-            self.position.x += effective_speed * delta;
+        self.movement = input / length.max(1.0);
+        self.facing_angle = input.y.atan2(input.x);
+        self.moving_direction = Direction::from_vector(input.x, input.y);
+
+        // Walking is replaced by the dash while it lasts
+        if self.dash.is_dashing() {
+            return;
         }
+        let speed = self.move_speed * self.calculate_speed_multiplier();
+        self.position.x += self.movement.x * speed * delta;
+        self.position.y += self.movement.y * speed * delta;
     }
 
     pub fn handle_status_effects(&mut self, delta: &f32) {
         // Process each status effect
         for status in self.statuses.iter_mut() {
-            match status {
-                Status::Poison(data) => {
-                    data.time_since_last_tick += delta;
-                    data.remaining_duration -= delta;
-
-                    if data.time_since_last_tick >= data.tick_interval {
-                        self.health -= data.damage_per_tick;
-                        data.time_since_last_tick = 0.0;
-                    }
-                }
-                Status::Burn(data) => {
-                    data.time_since_last_tick += delta;
-                    data.remaining_duration -= delta;
-
-                    if data.time_since_last_tick >= data.tick_interval {
-                        self.health -= data.damage_per_tick;
-                        data.time_since_last_tick = 0.0;
-                    }
-                }
-                Status::Slow(data) => {
-                    data.remaining_duration -= delta;
-                }
-                Status::Stun(data) => {
-                    data.remaining_duration -= delta;
-                }
-                Status::Regeneration(data) => {
-                    data.time_since_last_tick += delta;
-                    data.remaining_duration -= delta;
-
-                    if data.time_since_last_tick >= data.tick_interval {
-                        self.health += data.heal_per_tick;
-                        data.time_since_last_tick = 0.0;
-                    }
-                }
-                Status::SpeedBoost(data) => {
-                    data.remaining_duration -= delta;
-                }
-            }
+            self.health += status.tick(*delta);
         }
 
         // Clamp health between 0 and max_health
@@ -173,74 +382,80 @@ impl<'a> Player {
 
         // Remove expired statuses
         self.statuses.retain(|status| !status.is_expired());
+
+        self.vacuum_remaining = (self.vacuum_remaining - delta).max(0.0);
     }
 
-    pub fn handle_weapons(&mut self, delta: &f32) -> Vec<Projectile> {
+    pub fn handle_shield(&mut self, delta: &f32) {
+        self.time_since_hit += delta;
+        if self.time_since_hit >= SHIELD_RECHARGE_DELAY {
+            self.shield = (self.shield + SHIELD_RECHARGE_RATE * delta).min(self.max_shield);
+        }
+    }
+
+    pub fn handle_weapons(&mut self, all_enemies: &AllEnemies, delta: &f32) -> Vec<Projectile> {
         let mut res = vec![];
         let delta = *delta;
+        let trigger = TriggerState {
+            ready: !self.manual_fire || self.trigger_held,
+            reload_speed: self.reload_speed,
+        };
+        let aim = AimContext {
+            position: self.position,
+            mouse_angle: self.mouse_info.0,
+            facing_angle: self.facing_angle,
+            all_enemies,
+        };
         for slot in self.weapons.iter_mut() {
             let Some(weapon) = slot else { continue };
             match weapon {
                 Weapon::Bolter(data) => {
-                    data.time_since_last_tick += delta;
-
                     let offset = (self.texture.width / 2) as f32;
+                    let aim_mode = data.aim_mode;
+                    let damage = data.damage as i32;
                     let mut fire_bolter = || {
-                        let angle = self.mouse_info.0;
+                        let angle = aim.get_angle(aim_mode);
                         let position = Position {
                             x: self.position.x + angle.cos() * offset,
                             y: self.position.y + angle.sin() * offset,
                         };
-                        res.push(Projectile::Bolter(BolterProjectile::new(position, angle)));
+                        let bolt = if aim_mode == AimMode::NearestEnemy {
+                            Projectile::new_seeker_bolt(
+                                position,
+                                angle,
+                                self.ricochet_bounces,
+                                damage,
+                            )
+                        } else {
+                            Projectile::new_bolter(position, angle, self.ricochet_bounces, damage)
+                        };
+                        res.push(bolt);
                     };
 
-                    process_queued_shots(data, delta, &mut fire_bolter);
-
-                    if data.time_since_last_tick >= data.tick_interval {
-                        fire_bolter();
-                        enqueue_stacked_shots(data);
-                        data.time_since_last_tick = 0.0;
-                    }
+                    update_weapon(data, delta, &trigger, &mut fire_bolter);
                 }
                 Weapon::PowerSword(data) => {
-                    data.time_since_last_tick += delta;
                     let offset = (self.texture.width / 2) as f32;
 
+                    let aim_mode = data.aim_mode;
+                    let damage = data.damage as i32;
                     let mut fire_sword = || {
-                        let rotation = match self.moving_direction {
-                            Direction::Up => 1.0,
-                            Direction::Down => 1.0,
-                            Direction::Left => -1.0,
-                            Direction::Right => 1.0,
-                        };
+                        let angle = aim.get_angle(aim_mode);
                         let position = Position {
-                            x: self.position.x + (offset * rotation),
-                            y: self.position.y,
+                            x: self.position.x + angle.cos() * offset,
+                            y: self.position.y + angle.sin() * offset,
                         };
-                        res.push(Projectile::PowerSword(PowerSwordProjectile::new(
-                            position,
-                            self.moving_direction,
-                        )));
+                        res.push(Projectile::new_power_sword(position, angle, offset, damage));
                     };
 
-                    process_queued_shots(data, delta, &mut fire_sword);
-
-                    if data.time_since_last_tick >= data.tick_interval {
-                        fire_sword();
-                        enqueue_stacked_shots(data);
-                        data.time_since_last_tick = 0.0;
-                    }
+                    update_weapon(data, delta, &trigger, &mut fire_sword);
                 }
                 Weapon::Shotgun(data) => {
-                    data.time_since_last_tick += delta;
                     let offset = (self.texture.width / 2) as f32;
+                    let aim_mode = data.aim_mode;
+                    let damage = data.damage as i32;
                     let mut fire_shotgun = || {
-                        let base_angle = match self.moving_direction {
-                            Direction::Up => -PI / 2.0,
-                            Direction::Down => PI / 2.0,
-                            Direction::Left => PI,
-                            Direction::Right => 0.0,
-                        };
+                        let base_angle = aim.get_angle(aim_mode);
                         let spread = 10.0_f32.to_radians();
                         let angles = [
                             base_angle,
@@ -255,75 +470,118 @@ impl<'a> Player {
                                 x: self.position.x + angle.cos() * offset,
                                 y: self.position.y + angle.sin() * offset,
                             };
-                            res.push(Projectile::Shotgun(ShotgunProjectile::new(position, angle)));
+                            res.push(Projectile::new_shotgun_pellet(
+                                position,
+                                angle,
+                                self.ricochet_bounces,
+                                damage,
+                            ));
                         }
                     };
 
-                    process_queued_shots(data, delta, &mut fire_shotgun);
-
-                    if data.time_since_last_tick >= data.tick_interval {
-                        fire_shotgun();
-                        enqueue_stacked_shots(data);
-                        data.time_since_last_tick = 0.0;
-                    }
+                    update_weapon(data, delta, &trigger, &mut fire_shotgun);
                 }
                 Weapon::MultiMelta(data) => {
-                    data.time_since_last_tick += delta;
                     let offset = (self.texture.width / 2) as f32;
+                    let aim_mode = data.aim_mode;
+                    let damage = data.damage as i32;
                     let mut fire_melta = || {
-                        let angle = self.mouse_info.0;
+                        let angle = aim.get_angle(aim_mode);
                         let position = Position {
                             x: self.position.x + angle.cos() * offset,
                             y: self.position.y + angle.sin() * offset,
                         };
-                        res.push(Projectile::MultiMelta(MultiMeltaProjectile::new(
-                            position, angle,
-                        )));
+                        res.push(Projectile::new_melta_flame(position, angle, damage));
+                    };
+
+                    update_weapon(data, delta, &trigger, &mut fire_melta);
+                }
+                Weapon::GasGrenade(data) => {
+                    let aim_mode = data.aim_mode;
+                    let damage = data.damage as i32;
+                    let mut fire_grenade = || {
+                        let angle = aim.get_angle(aim_mode);
+                        let position = Position {
+                            x: self.position.x + angle.cos() * GRENADE_THROW_DISTANCE,
+                            y: self.position.y + angle.sin() * GRENADE_THROW_DISTANCE,
+                        };
+                        res.push(Projectile::new_hazard_zone(
+                            HazardKind::GasCloud,
+                            position,
+                            damage,
+                        ));
                     };
 
-                    process_queued_shots(data, delta, &mut fire_melta);
+                    update_weapon(data, delta, &trigger, &mut fire_grenade);
+                }
+                Weapon::Consecration(data) => {
+                    let damage = data.damage as i32;
+                    let mut fire_consecration = || {
+                        res.push(Projectile::new_hazard_zone(
+                            HazardKind::Consecration,
+                            self.position,
+                            damage,
+                        ));
+                    };
 
-                    if data.time_since_last_tick >= data.tick_interval {
-                        fire_melta();
-                        enqueue_stacked_shots(data);
-                        data.time_since_last_tick = 0.0;
-                    }
+                    update_weapon(data, delta, &trigger, &mut fire_consecration);
                 }
             }
         }
+
+        // Damage buffs apply to everything fired this frame
+        let damage_multiplier = self.calculate_damage_multiplier();
+        for projectile in res.iter_mut() {
+            projectile.damage = (projectile.damage as f32 * damage_multiplier).round() as i32;
+        }
         res
     }
 
-    fn calculate_speed_multiplier(&self) -> f32 {
-        let mut multiplier = 1.0;
-
-        for status in &self.statuses {
-            match status {
-                Status::Stun(_) => return 0.0, // Stun overrides everything
-                Status::Slow(data) => multiplier *= data.speed_multiplier,
-                Status::SpeedBoost(data) => multiplier *= data.speed_multiplier,
-                _ => {}
+    pub fn handle_abilities(
+        &mut self,
+        rl: &raylib::RaylibHandle,
+        input_map: &InputMap,
+        delta: &f32,
+    ) -> Vec<Projectile> {
+        for ability in self.abilities.iter_mut() {
+            ability.tick(*delta);
+        }
+
+        let mut res = vec![];
+        for (slot, action) in [Action::Ability1, Action::Ability2].into_iter().enumerate() {
+            if input_map.is_pressed(rl, action) {
+                res.append(&mut self.use_ability(slot));
             }
         }
+        res
+    }
+
+    /// Uses the ability in `slot` if it's off cooldown, aimed at the crosshair.
+    pub fn use_ability(&mut self, slot: usize) -> Vec<Projectile> {
+        let angle = self.mouse_info.get_angle();
+        let target = Position {
+            x: self.position.x + angle.cos() * self.aim_distance,
+            y: self.position.y + angle.sin() * self.aim_distance,
+        };
+        let Some(ability) = self.abilities.get_mut(slot) else {
+            return vec![];
+        };
+        if !ability.is_ready() {
+            return vec![];
+        }
+        ability.activate(self.position, target, &mut self.statuses)
+    }
+
+    fn calculate_speed_multiplier(&self) -> f32 {
+        calculate_speed_multiplier(&self.statuses)
+    }
 
-        multiplier
+    pub fn calculate_damage_multiplier(&self) -> f32 {
+        calculate_damage_multiplier(&self.statuses)
     }
 
     pub fn add_status(&mut self, status: Status) {
-        // Remove existing status of the same type (single instance rule)
-        self.statuses.retain(|s| {
-            !matches!(
-                (s, &status),
-                (Status::Poison(_), Status::Poison(_))
-                    | (Status::Burn(_), Status::Burn(_))
-                    | (Status::Slow(_), Status::Slow(_))
-                    | (Status::Stun(_), Status::Stun(_))
-                    | (Status::Regeneration(_), Status::Regeneration(_))
-                    | (Status::SpeedBoost(_), Status::SpeedBoost(_))
-            )
-        });
-
-        self.statuses.push(status);
+        add_status(&mut self.statuses, status);
     }
 
     pub fn get_active_status_names(&self) -> Vec<(String, f32)> {
@@ -381,16 +639,182 @@ impl<'a> Player {
     fn format_weapon_slot(weapon: &Weapon) -> String {
         let name = weapon.get_display_name();
         let count = weapon.get_stack_count();
-        if count > 1 {
+        let mut text = if count > 1 {
             format!("{} x{}", name, count)
         } else {
             name.to_string()
+        };
+        if weapon.is_aimed() {
+            text.push_str(&format!(
+                "\nAim: {}",
+                weapon.get_aim_mode().get_display_name()
+            ));
         }
+        text
+    }
+
+    pub fn can_dash(&self) -> bool {
+        let stunned = self
+            .statuses
+            .iter()
+            .any(|status| matches!(status, Status::Stun(_)));
+        self.dash.charges > 0 && !self.dash.is_dashing() && !stunned
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_remaining > 0.0
+    }
+
+    /// Every hit grants a short window of i-frames, so a crowd of enemies
+    /// landing on the same frame only hurts once. Armor comes off first, then
+    /// the shield soaks what it can before health takes the rest.
+    pub fn take_damage(&mut self, amount: i32) {
+        if self.is_invulnerable() || amount <= 0 {
+            return;
+        }
+        let scaled = (amount as f32 * self.damage_taken_multiplier).ceil() as i32;
+        let mut remaining = (scaled - self.armor).max(MIN_ARMORED_DAMAGE) as f32;
+
+        let absorbed = remaining.min(self.shield);
+        self.shield -= absorbed;
+        remaining -= absorbed;
+
+        self.health -= remaining.ceil() as i32;
+        self.invulnerable_remaining = self.hit_invulnerability;
+        self.time_since_hit = 0.0;
     }
 
     pub fn is_alive(&self) -> bool {
         self.health > 0
     }
+
+    pub fn is_vacuuming(&self) -> bool {
+        self.vacuum_remaining > 0.0
+    }
+
+    pub fn is_in_last_stand(&self) -> bool {
+        self.last_stand_remaining > 0.0
+    }
+
+    /// Runs after everything that can hurt the player this frame. A lethal
+    /// blow spends an Emperor's Blessing if there is one, otherwise the player
+    /// makes a last stand at 1 HP. The stand ends in death unless they get
+    /// healed or find a blessing before it runs out.
+    /// Returns true when a blessing was spent, so the caller can set off the shockwave.
+    pub fn handle_death_saves(&mut self, delta: f32) -> bool {
+        if self.is_in_last_stand() {
+            self.last_stand_remaining = (self.last_stand_remaining - delta).max(0.0);
+            if self.health > 1 {
+                // Patched up in time, the stand is over
+                self.end_last_stand();
+                return false;
+            }
+            if self.is_in_last_stand() && self.blessings == 0 {
+                self.health = 1;
+                return false;
+            }
+            // Out of time, or a blessing turned up
+            self.end_last_stand();
+            self.health = 0;
+        }
+        if self.health > 0 {
+            return false;
+        }
+
+        if self.blessings > 0 {
+            self.blessings -= 1;
+            self.health = ((self.max_health as f32 * REVIVE_HEALTH_FRACTION).ceil() as i32).max(1);
+            self.shield = self.max_shield;
+            self.invulnerable_remaining = REVIVE_INVULNERABILITY;
+            self.last_stand_used = false;
+            return true;
+        }
+        if self.last_stand_enabled && !self.last_stand_used {
+            self.last_stand_used = true;
+            self.health = 1;
+            self.last_stand_remaining = LAST_STAND_DURATION;
+            self.add_status(Status::DamageBoost(DamageBoostStatus {
+                damage_multiplier: LAST_STAND_DAMAGE_MULTIPLIER,
+                remaining_duration: LAST_STAND_DURATION,
+            }));
+        }
+        false
+    }
+
+    /// Stops the countdown and takes away the stand's damage boost.
+    fn end_last_stand(&mut self) {
+        self.last_stand_remaining = 0.0;
+        self.statuses
+            .retain(|status| !matches!(status, Status::DamageBoost(_)));
+    }
+}
+
+/// Snapshot of what lets the weapons fire this frame.
+struct TriggerState {
+    ready: bool,
+    reload_speed: f32,
+}
+
+/// Advances a weapon's timers, reloads its magazine and fires through
+/// `fire_shot` once it's ready.
+fn update_weapon<F>(data: &mut WeaponData, delta: f32, trigger: &TriggerState, fire_shot: &mut F)
+where
+    F: FnMut(),
+{
+    data.time_since_last_tick += delta;
+    if let Some(magazine) = data.magazine.as_mut() {
+        magazine.tick_reload(delta, trigger.reload_speed);
+    }
+
+    process_queued_shots(data, delta, fire_shot);
+
+    let has_ammo = data
+        .magazine
+        .as_ref()
+        .is_none_or(|magazine| !magazine.is_reloading());
+    if data.time_since_last_tick >= data.tick_interval && trigger.ready && has_ammo {
+        fire_shot();
+        enqueue_stacked_shots(data);
+        data.time_since_last_tick = 0.0;
+        if let Some(magazine) = data.magazine.as_mut() {
+            magazine.consume_round();
+        }
+    }
+}
+
+/// Snapshot of what the weapons can aim at this frame.
+struct AimContext<'a> {
+    position: Position,
+    mouse_angle: f32,
+    facing_angle: f32,
+    all_enemies: &'a AllEnemies<'a>,
+}
+
+impl AimContext<'_> {
+    fn get_angle(&self, aim_mode: AimMode) -> f32 {
+        match aim_mode {
+            AimMode::Mouse => self.mouse_angle,
+            AimMode::Movement => self.facing_angle,
+            AimMode::NearestEnemy => self.nearest_enemy_angle().unwrap_or(self.facing_angle),
+            AimMode::Random => rand::random_range(-PI..PI),
+        }
+    }
+
+    fn nearest_enemy_angle(&self) -> Option<f32> {
+        let mut closest: Option<(f32, f32)> = None;
+        for enemy in self.all_enemies.enemies.iter() {
+            let dx = enemy.position.x - self.position.x;
+            let dy = enemy.position.y - self.position.y;
+            let distance_sq = dx * dx + dy * dy;
+            if distance_sq > NEAREST_ENEMY_AIM_RANGE * NEAREST_ENEMY_AIM_RANGE {
+                continue;
+            }
+            if closest.is_none_or(|(best, _)| distance_sq < best) {
+                closest = Some((distance_sq, dy.atan2(dx)));
+            }
+        }
+        closest.map(|(_, angle)| angle)
+    }
 }
 
 fn enqueue_stacked_shots(data: &mut WeaponData) {
@@ -420,3 +844,177 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemy::tests::{create_test_enemies, create_test_player, fake_texture};
+
+    #[test]
+    fn test_diagonal_movement_is_normalized() {
+        let mut straight = create_test_player(0.0, 0.0);
+        straight.apply_movement(Vector2::new(1.0, 0.0), 1.0);
+
+        let mut diagonal = create_test_player(0.0, 0.0);
+        diagonal.apply_movement(Vector2::new(1.0, -1.0), 1.0);
+
+        let distance = (diagonal.position.x.powi(2) + diagonal.position.y.powi(2)).sqrt();
+        assert!((distance - straight.position.x).abs() < 0.01);
+        assert!((diagonal.facing_angle + PI / 4.0).abs() < 0.001);
+        assert_eq!(diagonal.moving_direction, Direction::Right);
+    }
+
+    #[test]
+    fn test_armor_and_shield_soak_damage() {
+        let mut player = create_test_player(0.0, 0.0);
+        player.hit_invulnerability = 0.0;
+        player.armor = 3;
+        player.max_shield = 10.0;
+        player.shield = 10.0;
+
+        player.take_damage(8);
+        assert_eq!(player.shield, 5.0, "Armor takes 3 off before the shield");
+        assert_eq!(player.health, 100);
+
+        player.take_damage(13);
+        assert_eq!(player.shield, 0.0);
+        assert_eq!(player.health, 95, "Overflow goes through to health");
+
+        player.take_damage(2);
+        assert_eq!(player.health, 94, "Armor never stops a hit entirely");
+    }
+
+    #[test]
+    fn test_hazard_zone_damage_comes_from_weapon_data() {
+        let texture = fake_texture(64);
+        let all_enemies = create_test_enemies(&texture);
+        let mut player = create_test_player(0.0, 0.0);
+        let mut consecration = Weapon::new_consecration();
+        if let Weapon::Consecration(data) = &mut consecration {
+            data.damage = 7.0;
+        }
+        player.weapons = [Some(consecration), None, None];
+
+        let zones = player.handle_weapons(&all_enemies, &5.0);
+        assert_eq!(zones.len(), 1);
+        assert_eq!(zones[0].damage, 7);
+    }
+
+    #[test]
+    fn test_shotgun_pellet_damage_comes_from_weapon_data() {
+        let texture = fake_texture(64);
+        let all_enemies = create_test_enemies(&texture);
+        let mut player = create_test_player(0.0, 0.0);
+        let mut shotgun = Weapon::new_shotgun();
+        if let Weapon::Shotgun(data) = &mut shotgun {
+            data.damage = 4.0;
+        }
+        player.weapons = [Some(shotgun), None, None];
+
+        let pellets = player.handle_weapons(&all_enemies, &5.0);
+        assert_eq!(pellets.len(), 5);
+        assert!(pellets.iter().all(|pellet| pellet.damage == 4));
+    }
+
+    #[test]
+    fn test_blessing_revives_on_lethal_damage() {
+        let mut player = create_test_player(0.0, 0.0);
+        player.blessings = 1;
+
+        player.take_damage(150);
+        assert!(player.handle_death_saves(0.016), "Blessing should be spent");
+        assert_eq!(player.blessings, 0);
+        assert_eq!(player.health, 50);
+        assert!(player.is_invulnerable());
+        assert!(!player.is_in_last_stand());
+    }
+
+    #[test]
+    fn test_last_stand_holds_at_one_hp_then_falls() {
+        let mut player = create_test_player(0.0, 0.0);
+        player.hit_invulnerability = 0.0;
+        player.blessings = 0;
+        player.last_stand_enabled = true;
+
+        player.take_damage(150);
+        assert!(!player.handle_death_saves(0.016));
+        assert!(player.is_in_last_stand());
+        assert_eq!(player.health, 1);
+        assert!(player.calculate_damage_multiplier() > 1.0);
+
+        player.take_damage(30);
+        player.handle_death_saves(1.0);
+        assert!(player.is_alive(), "Hits can't finish off a last stand");
+
+        player.handle_death_saves(LAST_STAND_DURATION);
+        assert!(!player.is_alive());
+        player.handle_death_saves(0.016);
+        assert!(!player.is_alive(), "Only one last stand per life");
+    }
+
+    #[test]
+    fn test_blessing_found_during_last_stand_revives() {
+        let mut player = create_test_player(0.0, 0.0);
+        player.blessings = 0;
+        player.last_stand_enabled = true;
+
+        player.take_damage(150);
+        player.handle_death_saves(0.016);
+        player.blessings = 1;
+
+        assert!(player.handle_death_saves(0.016));
+        assert!(!player.is_in_last_stand());
+        assert_eq!(player.health, 50);
+        assert!(!player.last_stand_used, "A fresh life gets a fresh stand");
+        assert_eq!(player.calculate_damage_multiplier(), 1.0);
+    }
+
+    #[test]
+    fn test_healing_during_last_stand_ends_it() {
+        let mut player = create_test_player(0.0, 0.0);
+        player.blessings = 0;
+        player.last_stand_enabled = true;
+
+        player.take_damage(150);
+        player.handle_death_saves(0.016);
+        assert!(player.calculate_damage_multiplier() > 1.0);
+
+        player.health += 20;
+        assert!(!player.handle_death_saves(0.016));
+        assert!(!player.is_in_last_stand());
+        assert_eq!(player.health, 21);
+        assert_eq!(player.calculate_damage_multiplier(), 1.0);
+    }
+
+    #[test]
+    fn test_shield_recharges_after_delay() {
+        let mut player = create_test_player(0.0, 0.0);
+        player.max_shield = 20.0;
+
+        player.handle_shield(&(SHIELD_RECHARGE_DELAY - 0.5));
+        assert_eq!(player.shield, 0.0, "Still waiting out the delay");
+
+        player.handle_shield(&1.0);
+        assert_eq!(player.shield, SHIELD_RECHARGE_RATE);
+
+        player.take_damage(5);
+        player.handle_shield(&1.0);
+        assert_eq!(player.shield, 5.0, "A hit restarts the delay");
+    }
+
+    #[test]
+    fn test_analog_input_moves_slower_and_deadzone_stands_still() {
+        let mut player = create_test_player(0.0, 0.0);
+        player.apply_movement(Vector2::new(0.0, 0.5), 1.0);
+        assert_eq!(player.position.y, player.move_speed * 0.5);
+        assert_eq!(player.moving_direction, Direction::Down);
+
+        let facing = player.facing_angle;
+        player.apply_movement(Vector2::new(0.05, 0.0), 1.0);
+        assert_eq!(player.position.x, 0.0);
+        assert_eq!(
+            player.facing_angle, facing,
+            "Stick drift keeps the old facing"
+        );
+    }
+}
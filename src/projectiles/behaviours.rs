@@ -0,0 +1,324 @@
+use raylib::prelude::*;
+
+use crate::{
+    enemy::AllEnemies,
+    player::Player,
+    projectiles::{presets::HazardKind, Projectile},
+    statuses::Status,
+    utils::Position,
+};
+
+/// How a projectile moves each frame.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Motion {
+    /// Flies straight along its angle.
+    Linear,
+    /// Stays where it was placed.
+    Stationary,
+    /// Sticks to the owner, `offset` px out along the projectile's angle.
+    AttachedToOwner { offset: f32 },
+    /// Circles the owner at `radius`, using the projectile angle as the orbit phase.
+    Orbit { radius: f32, angular_speed: f32 },
+    /// Flies forward, turning up to `turn_rate` rad/s towards the closest enemy.
+    Homing { turn_rate: f32 },
+}
+
+impl Motion {
+    /// Moves the projectile and returns how far it travelled this frame.
+    pub fn step(
+        &self,
+        position: &mut Position,
+        angle: &mut f32,
+        speed: f32,
+        owner: &Player,
+        all_enemies: &AllEnemies,
+        delta: f32,
+    ) -> f32 {
+        match self {
+            Motion::Linear => {
+                move_forward(position, *angle, speed * delta);
+                speed * delta
+            }
+            Motion::Stationary => 0.0,
+            Motion::AttachedToOwner { offset } => {
+                position.x = owner.position.x + angle.cos() * offset;
+                position.y = owner.position.y + angle.sin() * offset;
+                0.0
+            }
+            Motion::Orbit {
+                radius,
+                angular_speed,
+            } => {
+                *angle += angular_speed * delta;
+                position.x = owner.position.x + angle.cos() * radius;
+                position.y = owner.position.y + angle.sin() * radius;
+                (angular_speed * radius * delta).abs()
+            }
+            Motion::Homing { turn_rate } => {
+                if let Some(target) = closest_enemy_angle(all_enemies, position) {
+                    let mut turn = target - *angle;
+                    // Wrap into -PI..PI so we always turn the short way round
+                    turn = (turn + std::f32::consts::PI).rem_euclid(std::f32::consts::TAU)
+                        - std::f32::consts::PI;
+                    let max_turn = turn_rate * delta;
+                    *angle += turn.clamp(-max_turn, max_turn);
+                }
+                move_forward(position, *angle, speed * delta);
+                speed * delta
+            }
+        }
+    }
+}
+
+fn move_forward(position: &mut Position, angle: f32, distance: f32) {
+    position.x += angle.cos() * distance;
+    position.y += angle.sin() * distance;
+}
+
+fn closest_enemy_angle(all_enemies: &AllEnemies, position: &Position) -> Option<f32> {
+    let mut closest: Option<(f32, f32)> = None;
+    for enemy in all_enemies.enemies.iter() {
+        let dx = enemy.position.x - position.x;
+        let dy = enemy.position.y - position.y;
+        let distance_sq = dx * dx + dy * dy;
+        if closest.is_none_or(|(best, _)| distance_sq < best) {
+            closest = Some((distance_sq, dy.atan2(dx)));
+        }
+    }
+    closest.map(|(_, angle)| angle)
+}
+
+/// The area a projectile damages. Shapes that change over the projectile's
+/// life take `progress` (0.0 when fired, 1.0 when expired).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Shape {
+    /// Box centred on the projectile.
+    Rect {
+        width: f32,
+        height: f32,
+    },
+    Circle {
+        radius: f32,
+    },
+    /// Flame that widens from `width_start` to `width_end` as it travels.
+    Flame {
+        length: f32,
+        width_start: f32,
+        width_end: f32,
+    },
+    /// Blade extending `length` from the projectile, sweeping sideways across `sweep`.
+    Blade {
+        length: f32,
+        thickness: f32,
+        sweep: f32,
+    },
+}
+
+impl Shape {
+    /// Circles approximating the shape, as (center, radius).
+    pub fn collision_circles(
+        &self,
+        position: &Position,
+        angle: f32,
+        progress: f32,
+    ) -> Vec<(Vector2, f32)> {
+        let center = Vector2::new(position.x, position.y);
+        let forward = Vector2::new(angle.cos(), angle.sin());
+        match *self {
+            Shape::Rect { width, height } => vec![(center, width.min(height) / 2.0)],
+            Shape::Circle { radius } => vec![(center, radius)],
+            Shape::Flame { length, .. } => {
+                let radius = self.get_width(progress) * 0.5;
+                let half = length * 0.5;
+                vec![
+                    (center - forward * half, radius),
+                    (center, radius),
+                    (center + forward * half, radius),
+                ]
+            }
+            Shape::Blade {
+                length, thickness, ..
+            } => {
+                let base = self.get_blade_base(position, angle, progress);
+                let radius = thickness / 2.0;
+                let steps = (length / radius).ceil() as i32;
+                (0..=steps)
+                    .map(|step| (base + forward * (step as f32 * radius).min(length), radius))
+                    .collect()
+            }
+        }
+    }
+
+    pub fn collides_with_rect(
+        &self,
+        position: &Position,
+        angle: f32,
+        progress: f32,
+        rect: &Rectangle,
+    ) -> bool {
+        if let Shape::Rect { width, height } = *self {
+            let own_rect = Rectangle::new(
+                position.x - width / 2.0,
+                position.y - height / 2.0,
+                width,
+                height,
+            );
+            return own_rect.check_collision_recs(rect);
+        }
+        self.collision_circles(position, angle, progress)
+            .into_iter()
+            .any(|(center, radius)| rect.check_collision_circle_rec(center, radius))
+    }
+
+    pub fn collides_with_circle(
+        &self,
+        position: &Position,
+        angle: f32,
+        progress: f32,
+        other_center: Vector2,
+        other_radius: f32,
+    ) -> bool {
+        self.collision_circles(position, angle, progress)
+            .into_iter()
+            .any(|(center, radius)| center.distance_to(other_center) <= radius + other_radius)
+    }
+
+    /// Current width of a flame; other shapes report their fixed width.
+    pub fn get_width(&self, progress: f32) -> f32 {
+        match *self {
+            Shape::Rect { width, .. } => width,
+            Shape::Circle { radius } => radius * 2.0,
+            Shape::Flame {
+                width_start,
+                width_end,
+                ..
+            } => width_start + (width_end - width_start) * progress.clamp(0.0, 1.0),
+            Shape::Blade { thickness, .. } => thickness,
+        }
+    }
+
+    /// Where the blade starts this frame, shifted sideways by the sweep.
+    pub fn get_blade_base(&self, position: &Position, angle: f32, progress: f32) -> Vector2 {
+        let sweep = match *self {
+            Shape::Blade { sweep, .. } => sweep,
+            _ => 0.0,
+        };
+        let offset = (progress - 0.5) * sweep;
+        Vector2::new(
+            position.x - angle.sin() * offset,
+            position.y + angle.cos() * offset,
+        )
+    }
+}
+
+/// When a projectile is used up.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Lifetime {
+    /// Removed after this many hits (edge bounces count as hits).
+    Hits(i32),
+    /// Removed after travelling this far.
+    Range(f32),
+    /// Removed after this many seconds.
+    Time(f32),
+}
+
+/// Applied to every enemy a projectile hits.
+#[derive(Clone, Copy)]
+pub enum OnHitEffect {
+    ApplyStatus(Status),
+    /// Retarget towards the next closest enemy while hits remain.
+    Ricochet,
+}
+
+/// Triggered when a projectile's lifetime runs out (not when it is culled).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ExpireEffect {
+    /// Leaves a hazard zone that deals `damage` per tick.
+    SpawnHazard { kind: HazardKind, damage: i32 },
+    /// Blows up, damaging every enemy within `radius` once.
+    Detonate { radius: f32, damage: i32 },
+}
+
+impl ExpireEffect {
+    pub fn spawn(&self, position: Position) -> Projectile {
+        match self {
+            ExpireEffect::SpawnHazard { kind, damage } => {
+                Projectile::new_hazard_zone(*kind, position, *damage)
+            }
+            ExpireEffect::Detonate { radius, damage } => {
+                Projectile::new_blast(position, *radius, *damage)
+            }
+        }
+    }
+}
+
+/// How the renderer draws a projectile.
+#[derive(Clone, Copy, Debug)]
+pub enum Visual {
+    /// The bullet sprite, drawn at half size.
+    Bullet,
+    /// A small bright slug with a fading tail.
+    Tracer {
+        tail_length: f32,
+    },
+    /// The multi melta flame shader.
+    Flame,
+    Blade {
+        color: Color,
+    },
+    /// A glowing ball, used for enemy fire.
+    Orb {
+        color: Color,
+    },
+    /// Drawn on the ground layer under everything else.
+    Zone {
+        fill: Color,
+        edge: Color,
+    },
+    /// A fireball that swells and fades over the projectile's life.
+    Blast {
+        color: Color,
+    },
+    /// A target ring that fills in over `delay`, then a beam from above.
+    StrikeMarker {
+        delay: f32,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemy::tests::{create_test_enemies, create_test_player, fake_texture};
+    use crate::enemy::EnemyType;
+
+    #[test]
+    fn test_homing_turns_toward_closest_enemy() {
+        let texture = fake_texture(64);
+        let mut all_enemies = create_test_enemies(&texture);
+        all_enemies
+            .enemies
+            .push(EnemyType::new_servo_skull(Position { x: 0.0, y: 500.0 }));
+        all_enemies
+            .enemies
+            .push(EnemyType::new_servo_skull(Position { x: 0.0, y: -2000.0 }));
+        let owner = create_test_player(0.0, 0.0);
+
+        let motion = Motion::Homing { turn_rate: 2.0 };
+        let mut position = Position { x: 0.0, y: 0.0 };
+        let mut angle = 0.0;
+        motion.step(&mut position, &mut angle, 100.0, &owner, &all_enemies, 0.25);
+        assert!(
+            (angle - 0.5).abs() < 0.001,
+            "Turn is capped by the turn rate"
+        );
+
+        for _ in 0..10 {
+            motion.step(&mut position, &mut angle, 100.0, &owner, &all_enemies, 0.25);
+        }
+        let to_target = (500.0 - position.y).atan2(-position.x);
+        assert!(
+            (angle - to_target).abs() < 0.05,
+            "Locks onto the closer skull"
+        );
+    }
+}
@@ -2,23 +2,170 @@ use std::vec;
 
 use raylib::prelude::*;
 
-use crate::{enemy::AllEnemies, player::Player};
+use crate::{enemy::AllEnemies, player::Player, statuses::Status, utils::Position};
 
-pub mod bolter;
-pub mod multi_melta;
-pub mod power_sword;
-pub mod shotgun;
+pub mod behaviours;
+pub mod presets;
+
+use behaviours::{ExpireEffect, Lifetime, Motion, OnHitEffect, Shape, Visual};
 
 const SCREEN_HALF_WIDTH: f32 = 1240.0;
 const SCREEN_HALF_HEIGHT: f32 = 720.0;
 const CULL_BUFFER: f32 = 200.0; // Extra margin before removing
 
-#[derive(Clone, Copy)]
-pub enum Projectile {
-    Bolter(bolter::BolterProjectile),
-    MultiMelta(multi_melta::MultiMeltaProjectile),
-    PowerSword(power_sword::PowerSwordProjectile),
-    Shotgun(shotgun::ShotgunProjectile),
+// Ricochet tuning
+const RICOCHET_SEEK_RANGE: f32 = 400.0; // How far a bounced round looks for its next target
+const RICOCHET_HIT_COOLDOWN: f32 = 0.1; // Lets a bounced round leave the enemy it just hit
+
+/// A projectile is a combination of behaviours: how it moves, what area it
+/// damages, when it is used up and what it does on a hit. Weapons build them
+/// through the presets in `presets.rs`.
+#[derive(Clone)]
+pub struct Projectile {
+    pub position: Position,
+    pub angle: f32,
+    pub speed: f32,
+    pub damage: i32,
+    /// Ignores enemy armor.
+    pub pierces_armor: bool,
+
+    pub motion: Motion,
+    pub shape: Shape,
+    pub lifetime: Lifetime,
+    /// Seconds between damage ticks. `None` damages on every frame of overlap.
+    pub hit_interval: Option<f32>,
+    pub on_hit: Vec<OnHitEffect>,
+    pub on_expire: Option<ExpireEffect>,
+    /// Applied to the player while they overlap the projectile.
+    pub owner_statuses: Vec<Status>,
+    pub visual: Visual,
+
+    pub hits: i32,
+    pub distance_traveled: f32,
+    pub age: f32,
+    /// Collisions are ignored while this is above zero.
+    pub hit_cooldown: f32,
+}
+
+impl Projectile {
+    /// 0.0 when fired, 1.0 once the lifetime has run out.
+    pub fn get_progress(&self) -> f32 {
+        let progress = match self.lifetime {
+            Lifetime::Hits(max_hits) => self.hits as f32 / max_hits as f32,
+            Lifetime::Range(max_range) => self.distance_traveled / max_range,
+            Lifetime::Time(max_time) => self.age / max_time,
+        };
+        progress.clamp(0.0, 1.0)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        match self.lifetime {
+            Lifetime::Hits(max_hits) => self.hits >= max_hits,
+            Lifetime::Range(max_range) => self.distance_traveled >= max_range,
+            Lifetime::Time(max_time) => self.age >= max_time,
+        }
+    }
+
+    fn has_ricochet(&self) -> bool {
+        self.on_hit
+            .iter()
+            .any(|effect| matches!(effect, OnHitEffect::Ricochet))
+    }
+
+    /// True while a ricocheting round can bounce and still have a hit left over.
+    fn can_ricochet(&self) -> bool {
+        let Lifetime::Hits(max_hits) = self.lifetime else {
+            return false;
+        };
+        self.has_ricochet() && self.hits < max_hits - 1
+    }
+
+    pub fn handle_move(&mut self, player: &Player, all_enemies: &AllEnemies, delta: &f32) {
+        self.distance_traveled += self.motion.step(
+            &mut self.position,
+            &mut self.angle,
+            self.speed,
+            player,
+            all_enemies,
+            *delta,
+        );
+        self.age += delta;
+        self.hit_cooldown -= delta;
+
+        // Rounds with bounces left ricochet off the edge of the play area
+        if self.can_ricochet()
+            && bounce_off_world_edge(&mut self.position, &mut self.angle, &player.position)
+        {
+            self.hits += 1;
+        }
+    }
+
+    pub fn handle_collision(&mut self, all_enemies: &mut AllEnemies, player: &mut Player) {
+        if self.hit_cooldown > 0.0 || self.is_expired() {
+            return;
+        }
+
+        let progress = self.get_progress();
+        let mut hit_anything = false;
+        let mut bounce_from = None;
+        for (index, enemy) in all_enemies.enemies.iter_mut().enumerate() {
+            let texture = all_enemies
+                .texture_map
+                .get(&enemy.enemy_type)
+                .expect("unable to find texture");
+            let enemy_rec = enemy.get_collision_rect(texture);
+            if !self
+                .shape
+                .collides_with_rect(&self.position, self.angle, progress, &enemy_rec)
+            {
+                continue;
+            }
+
+            enemy.take_damage(self.damage, self.pierces_armor);
+            self.hits += 1;
+            hit_anything = true;
+            for effect in self.on_hit.iter() {
+                if let OnHitEffect::ApplyStatus(status) = effect {
+                    enemy.add_status(*status);
+                }
+            }
+
+            if self.is_expired() {
+                break;
+            }
+            if self.has_ricochet() {
+                bounce_from = Some(index);
+                break;
+            }
+        }
+
+        if !self.owner_statuses.is_empty() {
+            let player_center = Vector2::new(player.position.x, player.position.y);
+            if self.shape.collides_with_circle(
+                &self.position,
+                self.angle,
+                progress,
+                player_center,
+                player.collision_radius,
+            ) {
+                for status in self.owner_statuses.iter() {
+                    player.add_status(*status);
+                }
+                hit_anything = true;
+            }
+        }
+
+        if let Some(hit_index) = bounce_from {
+            if let Some(angle) = find_ricochet_angle(all_enemies, &self.position, hit_index) {
+                self.angle = angle;
+            }
+            self.hit_cooldown = RICOCHET_HIT_COOLDOWN;
+        } else if hit_anything {
+            if let Some(interval) = self.hit_interval {
+                self.hit_cooldown = interval;
+            }
+        }
+    }
 }
 
 pub struct AllProjectiles<'a> {
@@ -38,14 +185,9 @@ impl<'a> AllProjectiles<'a> {
         self.projectiles.append(new);
     }
 
-    pub fn move_projectiles(&mut self, player: &Player, delta: &f32) {
+    pub fn move_projectiles(&mut self, player: &Player, all_enemies: &AllEnemies, delta: &f32) {
         for projectile in self.projectiles.iter_mut() {
-            match projectile {
-                Projectile::Bolter(bolter_data) => bolter_data.handle_move(delta),
-                Projectile::MultiMelta(melta_data) => melta_data.handle_move(delta),
-                Projectile::PowerSword(sword_data) => sword_data.handle_move(player, delta),
-                Projectile::Shotgun(shotgun_data) => shotgun_data.handle_move(delta),
-            };
+            projectile.handle_move(player, all_enemies, delta);
         }
 
         // Remove projectiles that have left the visible area
@@ -55,35 +197,137 @@ impl<'a> AllProjectiles<'a> {
         let cull_bottom = player.position.y + SCREEN_HALF_HEIGHT + CULL_BUFFER;
 
         self.projectiles.retain(|projectile| {
-            let pos = match projectile {
-                Projectile::Bolter(b) => &b.position,
-                Projectile::MultiMelta(m) => &m.position,
-                Projectile::PowerSword(s) => &s.position,
-                Projectile::Shotgun(s) => &s.position,
-            };
+            let pos = &projectile.position;
             pos.x >= cull_left && pos.x <= cull_right && pos.y >= cull_top && pos.y <= cull_bottom
         });
     }
 
-    pub fn handle_collision(&mut self, all_enemies: &mut AllEnemies) {
+    pub fn handle_collision(&mut self, all_enemies: &mut AllEnemies, player: &mut Player) {
         for projectile in self.projectiles.iter_mut() {
-            match projectile {
-                Projectile::Bolter(bolter_data) => {
-                    bolter_data.handle_collision(all_enemies, self.texture)
-                }
-                Projectile::MultiMelta(melta_data) => melta_data.handle_collision(all_enemies),
-                Projectile::PowerSword(sword_data) => sword_data.handle_collision(all_enemies),
-                Projectile::Shotgun(shotgun_data) => shotgun_data.handle_collision(all_enemies),
-            };
+            projectile.handle_collision(all_enemies, player);
         }
 
-        self.projectiles.retain(|&projectile| match projectile {
-            Projectile::Bolter(bolter_projectile) => bolter_projectile.hits == 0,
-            Projectile::MultiMelta(melta_projectile) => {
-                melta_projectile.distance_traveled < melta_projectile.max_range
-            }
-            Projectile::PowerSword(sword_projectile) => sword_projectile.lifetime > 0.0,
-            Projectile::Shotgun(shotgun_projectile) => shotgun_projectile.hits == 0,
+        let mut spawned: Vec<Projectile> = self
+            .projectiles
+            .iter()
+            .filter(|projectile| projectile.is_expired())
+            .filter_map(|projectile| {
+                projectile
+                    .on_expire
+                    .map(|effect| effect.spawn(projectile.position))
+            })
+            .collect();
+
+        self.projectiles
+            .retain(|projectile| !projectile.is_expired());
+        self.projectiles.append(&mut spawned);
+    }
+}
+
+/// Angle from `position` towards the closest enemy (other than the one just hit)
+/// within ricochet range.
+pub fn find_ricochet_angle(
+    all_enemies: &AllEnemies,
+    position: &Position,
+    hit_index: usize,
+) -> Option<f32> {
+    let mut closest: Option<(f32, f32)> = None;
+    for (index, enemy) in all_enemies.enemies.iter().enumerate() {
+        if index == hit_index || enemy.health <= 0 {
+            continue;
+        }
+        let dx = enemy.position.x - position.x;
+        let dy = enemy.position.y - position.y;
+        let distance_sq = dx * dx + dy * dy;
+        if distance_sq > RICOCHET_SEEK_RANGE * RICOCHET_SEEK_RANGE {
+            continue;
+        }
+        if closest.is_none_or(|(best, _)| distance_sq < best) {
+            closest = Some((distance_sq, dy.atan2(dx)));
+        }
+    }
+    closest.map(|(_, angle)| angle)
+}
+
+/// Reflects `angle` off the edge of the visible play area around the player.
+/// Returns true if the projectile bounced.
+fn bounce_off_world_edge(position: &mut Position, angle: &mut f32, player_pos: &Position) -> bool {
+    let left = player_pos.x - SCREEN_HALF_WIDTH;
+    let right = player_pos.x + SCREEN_HALF_WIDTH;
+    let top = player_pos.y - SCREEN_HALF_HEIGHT;
+    let bottom = player_pos.y + SCREEN_HALF_HEIGHT;
+
+    let mut bounced = false;
+    if (position.x < left && angle.cos() < 0.0) || (position.x > right && angle.cos() > 0.0) {
+        *angle = std::f32::consts::PI - *angle;
+        position.x = position.x.clamp(left, right);
+        bounced = true;
+    }
+    if (position.y < top && angle.sin() < 0.0) || (position.y > bottom && angle.sin() > 0.0) {
+        *angle = -*angle;
+        position.y = position.y.clamp(top, bottom);
+        bounced = true;
+    }
+    bounced
+}
+
+/// Projectiles fired by enemies. These only ever collide with the player.
+pub struct EnemyProjectiles {
+    pub projectiles: Vec<Projectile>,
+}
+
+impl EnemyProjectiles {
+    pub fn new() -> Self {
+        EnemyProjectiles {
+            projectiles: vec![],
+        }
+    }
+
+    pub fn append(&mut self, new: &mut Vec<Projectile>) {
+        self.projectiles.append(new);
+    }
+
+    pub fn move_projectiles(&mut self, player: &Player, all_enemies: &AllEnemies, delta: &f32) {
+        for projectile in self.projectiles.iter_mut() {
+            projectile.handle_move(player, all_enemies, delta);
+        }
+
+        let cull_left = player.position.x - SCREEN_HALF_WIDTH - CULL_BUFFER;
+        let cull_right = player.position.x + SCREEN_HALF_WIDTH + CULL_BUFFER;
+        let cull_top = player.position.y - SCREEN_HALF_HEIGHT - CULL_BUFFER;
+        let cull_bottom = player.position.y + SCREEN_HALF_HEIGHT + CULL_BUFFER;
+
+        self.projectiles.retain(|projectile| {
+            let pos = &projectile.position;
+            pos.x >= cull_left && pos.x <= cull_right && pos.y >= cull_top && pos.y <= cull_bottom
         });
     }
+
+    pub fn handle_collision(&mut self, player: &mut Player) {
+        let player_center = Vector2::new(player.position.x, player.position.y);
+        for projectile in self.projectiles.iter_mut() {
+            if projectile.is_expired() {
+                continue;
+            }
+            if projectile.shape.collides_with_circle(
+                &projectile.position,
+                projectile.angle,
+                projectile.get_progress(),
+                player_center,
+                player.collision_radius,
+            ) {
+                player.take_damage(projectile.damage);
+                projectile.hits += 1;
+            }
+        }
+
+        self.projectiles
+            .retain(|projectile| !projectile.is_expired());
+    }
+}
+
+impl Default for EnemyProjectiles {
+    fn default() -> Self {
+        Self::new()
+    }
 }
@@ -0,0 +1,332 @@
+use raylib::prelude::*;
+
+use crate::{
+    projectiles::{
+        behaviours::{ExpireEffect, Lifetime, Motion, OnHitEffect, Shape, Visual},
+        Projectile,
+    },
+    statuses::{
+        BurnStatus, PoisonStatus, RegenerationStatus, SlowStatus, SpeedBoostStatus, Status,
+    },
+    utils::Position,
+};
+
+// bullet_new.png is drawn at half its 64px size
+const BULLET_SIZE: f32 = 32.0;
+const ZONE_TICK_INTERVAL: f32 = 0.5;
+const SEEKER_TURN_RATE: f32 = 4.0; // Radians per second
+const PROMETHIUM_POOL_DAMAGE: i32 = 1; // Per tick, left behind by melta flames
+const BLAST_TIME: f32 = 0.3;
+const STRIKE_BEAM_TIME: f32 = 0.3;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum HazardKind {
+    /// Burning promethium left behind by the Multi Melta.
+    PromethiumPool,
+    /// Poison gas from a thrown grenade, also slows enemies.
+    GasCloud,
+    /// Holy ground that damages enemies and heals/buffs the player.
+    Consecration,
+}
+
+impl Projectile {
+    /// A bare projectile; the presets below fill in the behaviours.
+    pub fn new(
+        position: Position,
+        angle: f32,
+        motion: Motion,
+        shape: Shape,
+        lifetime: Lifetime,
+        visual: Visual,
+    ) -> Self {
+        Projectile {
+            position,
+            angle,
+            speed: 0.0,
+            damage: 0,
+            pierces_armor: false,
+            motion,
+            shape,
+            lifetime,
+            hit_interval: None,
+            on_hit: vec![],
+            on_expire: None,
+            owner_statuses: vec![],
+            visual,
+            hits: 0,
+            distance_traveled: 0.0,
+            age: 0.0,
+            hit_cooldown: 0.0,
+        }
+    }
+
+    pub fn new_bolter(position: Position, angle: f32, ricochets: i32, damage: i32) -> Self {
+        let mut bolter = Projectile::new(
+            position,
+            angle,
+            Motion::Linear,
+            Shape::Rect {
+                width: BULLET_SIZE,
+                height: BULLET_SIZE,
+            },
+            Lifetime::Hits(1),
+            Visual::Bullet,
+        );
+        bolter.speed = 1000.0;
+        bolter.damage = damage;
+        bolter.with_ricochets(ricochets)
+    }
+
+    /// Bolter round that keeps curving towards the closest enemy. Fired when
+    /// the bolter is set to Nearest Enemy aim.
+    pub fn new_seeker_bolt(position: Position, angle: f32, ricochets: i32, damage: i32) -> Self {
+        let mut bolt = Projectile::new_bolter(position, angle, ricochets, damage);
+        bolt.motion = Motion::Homing {
+            turn_rate: SEEKER_TURN_RATE,
+        };
+        bolt
+    }
+
+    pub fn new_shotgun_pellet(position: Position, angle: f32, ricochets: i32, damage: i32) -> Self {
+        let mut pellet = Projectile::new(
+            position,
+            angle,
+            Motion::Linear,
+            Shape::Rect {
+                width: 12.0,
+                height: 6.0,
+            },
+            Lifetime::Hits(1),
+            Visual::Tracer { tail_length: 14.0 },
+        );
+        pellet.speed = 900.0;
+        pellet.damage = damage;
+        pellet.with_ricochets(ricochets)
+    }
+
+    pub fn new_melta_flame(position: Position, angle: f32, damage: i32) -> Self {
+        let mut flame = Projectile::new(
+            position,
+            angle,
+            Motion::Linear,
+            Shape::Flame {
+                length: 50.0,
+                width_start: 28.0,
+                width_end: 140.0,
+            },
+            Lifetime::Range(350.0),
+            Visual::Flame,
+        );
+        flame.speed = 1000.0;
+        flame.damage = damage;
+        // Melta burns straight through armor
+        flame.pierces_armor = true;
+        // Flames that burn out leave a pool of promethium behind
+        flame.on_expire = Some(ExpireEffect::SpawnHazard {
+            kind: HazardKind::PromethiumPool,
+            damage: PROMETHIUM_POOL_DAMAGE,
+        });
+        flame
+    }
+
+    pub fn new_power_sword(position: Position, angle: f32, offset: f32, damage: i32) -> Self {
+        let mut sword = Projectile::new(
+            position,
+            angle,
+            Motion::AttachedToOwner { offset },
+            Shape::Blade {
+                length: 120.0,
+                thickness: 20.0,
+                sweep: 250.0,
+            },
+            Lifetime::Time(0.25),
+            Visual::Blade { color: Color::BLUE },
+        );
+        sword.damage = damage;
+        sword
+    }
+
+    /// Techmarine servo-skull. Circles the player for the whole run, ramming
+    /// anything it passes.
+    pub fn new_servo_skull_companion(position: Position, angle: f32) -> Self {
+        let mut skull = Projectile::new(
+            position,
+            angle,
+            Motion::Orbit {
+                radius: 110.0,
+                angular_speed: 2.5,
+            },
+            Shape::Circle { radius: 14.0 },
+            Lifetime::Time(f32::INFINITY),
+            Visual::Orb {
+                color: Color::new(255, 200, 80, 255),
+            },
+        );
+        skull.damage = 6;
+        skull.hit_interval = Some(0.4);
+        skull
+    }
+
+    /// Lobbed over enemies' heads, exploding once it has flown `distance`.
+    pub fn new_frag_grenade(
+        position: Position,
+        angle: f32,
+        distance: f32,
+        radius: f32,
+        damage: i32,
+    ) -> Self {
+        let mut grenade = Projectile::new(
+            position,
+            angle,
+            Motion::Linear,
+            Shape::Circle { radius: 8.0 },
+            Lifetime::Range(distance),
+            Visual::Orb {
+                color: Color::DARKGREEN,
+            },
+        );
+        grenade.speed = 800.0;
+        // Never collides in flight
+        grenade.hit_cooldown = f32::INFINITY;
+        grenade.on_expire = Some(ExpireEffect::Detonate { radius, damage });
+        grenade
+    }
+
+    /// Damages everything inside `radius` once, on the first frame it touches anything.
+    pub fn new_blast(position: Position, radius: f32, damage: i32) -> Self {
+        let mut blast = Projectile::new(
+            position,
+            0.0,
+            Motion::Stationary,
+            Shape::Circle { radius },
+            Lifetime::Time(BLAST_TIME),
+            Visual::Blast {
+                color: Color::ORANGE,
+            },
+        );
+        blast.damage = damage;
+        blast.hit_interval = Some(f32::INFINITY);
+        blast
+    }
+
+    /// Harmless target marker for `delay` seconds, then a beam that hits
+    /// everything inside `radius` once, straight through armor.
+    pub fn new_orbital_lance(position: Position, radius: f32, damage: i32, delay: f32) -> Self {
+        let mut lance = Projectile::new(
+            position,
+            0.0,
+            Motion::Stationary,
+            Shape::Circle { radius },
+            Lifetime::Time(delay + STRIKE_BEAM_TIME),
+            Visual::StrikeMarker { delay },
+        );
+        lance.damage = damage;
+        lance.pierces_armor = true;
+        lance.hit_cooldown = delay;
+        lance.hit_interval = Some(f32::INFINITY);
+        lance
+    }
+
+    /// Slow glowing round fired by ranged enemies, only collides with the player.
+    pub fn new_enemy_bolt(position: Position, angle: f32) -> Self {
+        let mut bolt = Projectile::new(
+            position,
+            angle,
+            Motion::Linear,
+            Shape::Circle { radius: 8.0 },
+            Lifetime::Hits(1),
+            Visual::Orb {
+                color: Color::new(200, 60, 255, 255),
+            },
+        );
+        bolt.speed = 450.0;
+        bolt.damage = 8;
+        bolt
+    }
+
+    /// A ground-persisting area that deals `damage` and applies its effects to
+    /// everything inside it on a tick interval.
+    pub fn new_hazard_zone(kind: HazardKind, position: Position, damage: i32) -> Self {
+        let (radius, duration) = match kind {
+            HazardKind::PromethiumPool => (70.0, 3.0),
+            HazardKind::GasCloud => (120.0, 5.0),
+            HazardKind::Consecration => (150.0, 4.0),
+        };
+        let (fill, edge) = match kind {
+            HazardKind::PromethiumPool => {
+                (Color::new(255, 110, 0, 90), Color::new(255, 60, 0, 180))
+            }
+            HazardKind::GasCloud => (Color::new(90, 200, 40, 80), Color::new(60, 160, 20, 160)),
+            HazardKind::Consecration => {
+                (Color::new(255, 215, 0, 60), Color::new(255, 235, 120, 200))
+            }
+        };
+
+        let mut zone = Projectile::new(
+            position,
+            0.0,
+            Motion::Stationary,
+            Shape::Circle { radius },
+            Lifetime::Time(duration),
+            Visual::Zone { fill, edge },
+        );
+        zone.damage = damage;
+        zone.hit_interval = Some(ZONE_TICK_INTERVAL);
+        zone.on_hit = hazard_enemy_statuses(kind)
+            .into_iter()
+            .map(OnHitEffect::ApplyStatus)
+            .collect();
+        zone.owner_statuses = hazard_player_statuses(kind);
+        zone
+    }
+
+    fn with_ricochets(mut self, ricochets: i32) -> Self {
+        if ricochets > 0 {
+            self.lifetime = Lifetime::Hits(1 + ricochets);
+            self.on_hit.push(OnHitEffect::Ricochet);
+        }
+        self
+    }
+}
+
+fn hazard_enemy_statuses(kind: HazardKind) -> Vec<Status> {
+    match kind {
+        HazardKind::PromethiumPool => vec![Status::Burn(BurnStatus {
+            damage_per_tick: 2,
+            tick_interval: 0.5,
+            remaining_duration: 2.0,
+            time_since_last_tick: 0.0,
+        })],
+        HazardKind::GasCloud => vec![
+            Status::Poison(PoisonStatus {
+                damage_per_tick: 1,
+                tick_interval: 0.5,
+                remaining_duration: 3.0,
+                time_since_last_tick: 0.0,
+            }),
+            Status::Slow(SlowStatus {
+                speed_multiplier: 0.5,
+                remaining_duration: 1.0,
+            }),
+        ],
+        HazardKind::Consecration => vec![],
+    }
+}
+
+fn hazard_player_statuses(kind: HazardKind) -> Vec<Status> {
+    match kind {
+        HazardKind::Consecration => vec![
+            Status::Regeneration(RegenerationStatus {
+                heal_per_tick: 2,
+                tick_interval: 0.5,
+                remaining_duration: 1.0,
+                time_since_last_tick: 0.0,
+            }),
+            Status::SpeedBoost(SpeedBoostStatus {
+                speed_multiplier: 1.25,
+                remaining_duration: 1.0,
+            }),
+        ],
+        HazardKind::PromethiumPool | HazardKind::GasCloud => vec![],
+    }
+}
@@ -1,17 +1,30 @@
 use crate::{
-    enemy::AllEnemies,
+    classes::ALL_CLASSES,
+    difficulty::ALL_DIFFICULTIES,
+    drops::{AllDrops, DropKind},
+    enemy::{AllEnemies, EnemyType, EXPLOSION_FADE_TIME},
     game_state::{self, GameState, MultiMeltaShader},
+    input::{Action, InputDevice, InputMap},
+    menu::{get_controls_rows, ClassSelect, ControlsRow, ControlsScreen, PauseMenu, PAUSE_OPTIONS},
     player::{Player, PLAYER_SCALE},
     projectiles::AllProjectiles,
-    utils::Direction,
+    utils::{Direction, Position},
     weapon_pickups::AllWeaponPickups,
+    weapons::Magazine,
 };
 use raylib::{color::Color, prelude::*};
 
-use crate::projectiles::Projectile;
+use crate::projectiles::{
+    behaviours::{Shape, Visual},
+    Projectile,
+};
 
 pub mod background;
 
+const CHARGE_TELEGRAPH_LENGTH: f32 = 400.0;
+// Health and shield points per box on the HUD bars
+const BAR_SEGMENT_SIZE: f32 = 10.0;
+
 pub fn render_game_state(game_state: &mut GameState, thread: &raylib::RaylibThread) {
     let fps = game_state.rl.get_fps();
     let _time = game_state.rl.get_time();
@@ -46,22 +59,62 @@ pub fn render_game_state(game_state: &mut GameState, thread: &raylib::RaylibThre
             Vector2::new(game_state.player.position.x, game_state.player.position.y);
         game_state.background.render(&mut d2, camera_target);
 
+        // Ground effects sit below every entity
+        render_hazard_zones(&mut d2, &game_state.projectiles);
+
         // Game entities (normal layer)
         render_weapon_pickups(&mut d2, &game_state.weapon_pickups);
+        render_drops(&mut d2, &game_state.drops);
         render_player(&mut d2, &game_state.player);
         render_projectiles(
             &mut d2,
-            &game_state.projectiles,
+            &game_state.projectiles.projectiles,
+            game_state.projectiles.texture,
             &game_state.white_texture,
             &mut game_state.multi_melta_shader,
             game_state.elapsed_time,
         );
         render_enemies(&mut d2, &game_state.enemies);
-        render_crosshair(&mut d2, mouse_world);
+        render_explosions(&mut d2, &game_state.enemies);
+        render_projectiles(
+            &mut d2,
+            &game_state.enemy_projectiles.projectiles,
+            game_state.projectiles.texture,
+            &game_state.white_texture,
+            &mut game_state.multi_melta_shader,
+            game_state.elapsed_time,
+        );
+        let crosshair = match game_state.player.input_device {
+            InputDevice::KeyboardMouse => mouse_world,
+            InputDevice::Gamepad => {
+                let angle = game_state.player.mouse_info.get_angle();
+                let distance = game_state.player.aim_distance;
+                Vector2::new(
+                    game_state.player.position.x + angle.cos() * distance,
+                    game_state.player.position.y + angle.sin() * distance,
+                )
+            }
+        };
+        render_crosshair(&mut d2, crosshair);
     }
 
     // === SCREEN SPACE UI (after camera - draws on top) ===
-    d.draw_text("Use WASD to move", 10, 10, 20, Color::WHITE);
+    let move_keys: Vec<String> = [
+        Action::MoveUp,
+        Action::MoveLeft,
+        Action::MoveDown,
+        Action::MoveRight,
+    ]
+    .iter()
+    .map(|action| game_state.input_map.get_label(*action))
+    .collect();
+    d.draw_text(
+        &format!("Use {} to move", move_keys.join("/")),
+        10,
+        10,
+        20,
+        Color::WHITE,
+    );
     d.draw_text(&format!("FPS: {}", fps), 400, 10, 20, Color::GREEN);
 
     // Game Clock - centered at top
@@ -73,8 +126,11 @@ pub fn render_game_state(game_state: &mut GameState, thread: &raylib::RaylibThre
     let clock_x = (screen_width / 2) - (text_width / 2);
     d.draw_text(&time_text, clock_x, 10, 20, Color::WHITE);
 
+    render_boss_health_bar(&mut d, &game_state.enemies);
+    render_announcement(&mut d, &game_state.enemies);
     render_player_ui(&mut d, &game_state.player);
-    render_weapon_slots(&mut d, &game_state.player);
+    render_last_stand(&mut d, &game_state.player);
+    render_weapon_slots(&mut d, &game_state.player, &game_state.input_map);
 
     if game_state::DEBUG_MODE {
         render_debug_stats(
@@ -84,6 +140,203 @@ pub fn render_game_state(game_state: &mut GameState, thread: &raylib::RaylibThre
             &game_state.projectiles,
         );
     }
+
+    if game_state.pause_menu.open {
+        render_pause_menu(&mut d, &game_state.pause_menu, &game_state.input_map);
+    }
+}
+
+/// Dims the frozen game and lists the pause options, highlighting the selected one.
+fn render_pause_menu(d: &mut RaylibDrawHandle, menu: &PauseMenu, input_map: &InputMap) {
+    let screen_width = d.get_screen_width();
+    let screen_height = d.get_screen_height();
+    d.draw_rectangle(0, 0, screen_width, screen_height, Color::BLACK.fade(0.6));
+    if let Some(controls) = &menu.controls {
+        render_controls_screen(d, controls, input_map);
+        return;
+    }
+
+    let title = "PAUSED";
+    let title_size = 64;
+    let title_width = d.measure_text(title, title_size);
+    let mut y = screen_height / 3;
+    d.draw_text(
+        title,
+        screen_width / 2 - title_width / 2,
+        y,
+        title_size,
+        Color::WHITE,
+    );
+    y += title_size + 30;
+
+    let font_size = 32;
+    for (i, option) in PAUSE_OPTIONS.iter().enumerate() {
+        let selected = i == menu.selected;
+        let text = if selected {
+            format!("> {} <", option.get_display_name())
+        } else {
+            option.get_display_name().to_string()
+        };
+        let text_width = d.measure_text(&text, font_size);
+        let color = if selected {
+            Color::GOLD
+        } else {
+            Color::LIGHTGRAY
+        };
+        d.draw_text(
+            &text,
+            screen_width / 2 - text_width / 2,
+            y,
+            font_size,
+            color,
+        );
+        y += font_size + 12;
+    }
+}
+
+/// Class list down the left, with the selected class's marine, stats and
+/// passive alongside it.
+pub fn render_class_select(
+    rl: &mut RaylibHandle,
+    thread: &raylib::RaylibThread,
+    select: &ClassSelect,
+    player_texture: &Texture2D,
+    input_map: &InputMap,
+) {
+    let mut d = rl.begin_drawing(thread);
+    d.clear_background(Color::BLACK);
+    if select.choosing_difficulty {
+        render_difficulty_select(&mut d, select, input_map);
+        return;
+    }
+
+    let screen_width = d.get_screen_width();
+    let screen_height = d.get_screen_height();
+    let list_x = screen_width / 4;
+    let detail_x = screen_width / 2;
+    let mut y = screen_height / 4;
+
+    d.draw_text("CHOOSE YOUR MARINE", list_x, y - 100, 48, Color::WHITE);
+    d.draw_text(
+        &format!(
+            "{}/{} to choose, {} to continue",
+            input_map.get_label(Action::MenuUp),
+            input_map.get_label(Action::MenuDown),
+            input_map.get_label(Action::MenuConfirm)
+        ),
+        list_x,
+        y - 40,
+        18,
+        Color::LIGHTGRAY,
+    );
+
+    let font_size = 32;
+    for (i, class) in ALL_CLASSES.iter().enumerate() {
+        let selected = i == select.selected;
+        let color = if selected {
+            Color::GOLD
+        } else {
+            Color::LIGHTGRAY
+        };
+        let prefix = if selected { "> " } else { "  " };
+        d.draw_text(
+            &format!("{}{}", prefix, class.get_display_name()),
+            list_x,
+            y + i as i32 * (font_size + 16),
+            font_size,
+            color,
+        );
+    }
+
+    let class = select.get_selected();
+    let portrait_size = 192.0;
+    d.draw_texture_pro(
+        player_texture,
+        Rectangle::new(
+            0.0,
+            0.0,
+            player_texture.width as f32,
+            player_texture.height as f32,
+        ),
+        Rectangle::new(detail_x as f32, y as f32, portrait_size, portrait_size),
+        Vector2::zero(),
+        0.0,
+        class.get_tint(),
+    );
+    y += portrait_size as i32 + 20;
+
+    let weapon = class.get_starting_weapon();
+    let lines = [
+        format!("Health: {}", class.get_max_health()),
+        format!(
+            "Armor: {}  Shield: {:.0}",
+            class.get_armor(),
+            class.get_max_shield()
+        ),
+        format!("Speed: {:.0}", class.get_move_speed()),
+        format!("Starting Weapon: {}", weapon.get_display_name()),
+        format!(
+            "Abilities: {}",
+            class
+                .get_abilities()
+                .iter()
+                .map(|ability| ability.get_display_name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        format!("Passive - {}:", class.get_passive_name()),
+        format!("  {}", class.get_passive_description()),
+    ];
+    for line in lines.iter() {
+        d.draw_text(line, detail_x, y, 22, Color::WHITE);
+        y += 30;
+    }
+}
+
+/// Difficulty list, shown after a class is picked.
+fn render_difficulty_select(d: &mut RaylibDrawHandle, select: &ClassSelect, input_map: &InputMap) {
+    let list_x = d.get_screen_width() / 4;
+    let y = d.get_screen_height() / 4;
+
+    d.draw_text("CHOOSE DIFFICULTY", list_x, y - 100, 48, Color::WHITE);
+    d.draw_text(
+        &format!(
+            "{} to deploy as {}, {} to go back",
+            input_map.get_label(Action::MenuConfirm),
+            select.get_selected().get_display_name(),
+            input_map.get_label(Action::MenuBack)
+        ),
+        list_x,
+        y - 40,
+        18,
+        Color::LIGHTGRAY,
+    );
+
+    let font_size = 32;
+    for (i, difficulty) in ALL_DIFFICULTIES.iter().enumerate() {
+        let selected = i == select.difficulty_selected;
+        let color = if selected {
+            Color::GOLD
+        } else {
+            Color::LIGHTGRAY
+        };
+        let prefix = if selected { "> " } else { "  " };
+        let row_y = y + i as i32 * (font_size + 40);
+        d.draw_text(
+            &format!("{}{}", prefix, difficulty.get_display_name()),
+            list_x,
+            row_y,
+            font_size,
+            color,
+        );
+        d.draw_text(
+            difficulty.get_description(),
+            list_x + 40,
+            row_y + font_size + 4,
+            18,
+            Color::GRAY,
+        );
+    }
 }
 
 fn render_player(d: &mut RaylibMode2D<RaylibDrawHandle>, player: &Player) {
@@ -105,6 +358,14 @@ fn render_player(d: &mut RaylibMode2D<RaylibDrawHandle>, player: &Player) {
         player.texture.height as f32 / (PLAYER_SCALE * 2.0),
     );
     let rotation = 0.0; // No rotation, just flipping
+
+    // Flicker while the player can't be hurt
+    let tint = if player.is_invulnerable() && (player.invulnerable_remaining * 20.0) as i32 % 2 == 0
+    {
+        player.class.get_tint().fade(0.3)
+    } else {
+        player.class.get_tint()
+    };
     if game_state::DEBUG_MODE {
         d.draw_circle_lines(
             player.position.x as i32,
@@ -112,6 +373,12 @@ fn render_player(d: &mut RaylibMode2D<RaylibDrawHandle>, player: &Player) {
             player.collision_radius,
             Color::RED,
         );
+        d.draw_circle_lines(
+            player.position.x as i32,
+            player.position.y as i32,
+            player.magnet_radius,
+            Color::VIOLET.fade(0.4),
+        );
     }
 
     d.draw_texture_pro(
@@ -120,42 +387,71 @@ fn render_player(d: &mut RaylibMode2D<RaylibDrawHandle>, player: &Player) {
         dest_rec,
         origin,
         rotation,
-        Color::WHITE,
+        tint,
     );
 }
 
 fn render_player_ui(d: &mut RaylibDrawHandle, player: &Player) {
-    // Draw health with color coding
-    let health_color = if player.health < 30 {
+    // Shield sits on top of health, both split into 10 point segments
+    if player.max_shield > 0.0 {
+        render_segmented_bar(
+            d,
+            Vector2::new(10.0, 32.0),
+            8.0,
+            player.shield,
+            player.max_shield,
+            Color::SKYBLUE,
+        );
+    }
+
+    // Health turns yellow, then red, as it runs low
+    let health_fraction = player.health as f32 / player.max_health as f32;
+    let health_color = if health_fraction < 0.3 {
         Color::RED
-    } else if player.health < 60 {
+    } else if health_fraction < 0.6 {
         Color::YELLOW
     } else {
-        Color::WHITE
+        Color::GREEN
     };
-    d.draw_text(
-        &format!("Health: {:.0}/{:.0}", player.health, player.max_health),
-        10,
-        30,
-        20,
+    let health_width = render_segmented_bar(
+        d,
+        Vector2::new(10.0, 44.0),
+        14.0,
+        player.health as f32,
+        player.max_health as f32,
         health_color,
     );
+    let mut label_x = 10 + health_width as i32 + 10;
+    if player.armor > 0 {
+        let text = format!("Armor {}", player.armor);
+        d.draw_text(&text, label_x, 43, 16, Color::LIGHTGRAY);
+        label_x += d.measure_text(&text, 16) + 10;
+    }
+    if player.blessings > 0 {
+        d.draw_text(
+            &format!("Blessings {}", player.blessings),
+            label_x,
+            43,
+            16,
+            Color::GOLD,
+        );
+    }
 
     // Draw active status effects
     let active_statuses = player.get_active_status_names();
     d.draw_text(
         &format!("Active Status Effects: {}", active_statuses.len()),
         10,
-        50,
+        66,
         20,
         Color::LIGHTGRAY,
     );
 
-    let mut y_offset = 70;
+    let mut y_offset = 86;
     for (name, duration) in active_statuses {
         let status_color = match name.as_str() {
             "Poison" | "Burn" | "Slow" | "Stun" => Color::RED,
-            "Regeneration" | "Speed Boost" => Color::GREEN,
+            "Regeneration" | "Speed Boost" | "Damage Boost" => Color::GREEN,
             _ => Color::WHITE,
         };
 
@@ -170,17 +466,240 @@ fn render_player_ui(d: &mut RaylibDrawHandle, player: &Player) {
     }
 }
 
+/// Red pulsing border and a countdown while the player is making a last stand.
+fn render_last_stand(d: &mut RaylibDrawHandle, player: &Player) {
+    if !player.is_in_last_stand() {
+        return;
+    }
+    let screen_width = d.get_screen_width();
+    let screen_height = d.get_screen_height();
+    let pulse = 0.5 + 0.5 * (player.last_stand_remaining * 8.0).sin();
+    d.draw_rectangle_lines_ex(
+        Rectangle::new(0.0, 0.0, screen_width as f32, screen_height as f32),
+        12.0,
+        Color::RED.fade(0.4 + 0.4 * pulse),
+    );
+
+    let text = format!("LAST STAND {:.1}s", player.last_stand_remaining);
+    let text_width = d.measure_text(&text, 30);
+    d.draw_text(
+        &text,
+        screen_width / 2 - text_width / 2,
+        screen_height / 4,
+        30,
+        Color::RED,
+    );
+}
+
+/// One box per `BAR_SEGMENT_SIZE` points of `max`, filled up to `value`.
+/// Returns how wide the bar ended up.
+fn render_segmented_bar(
+    d: &mut RaylibDrawHandle,
+    position: Vector2,
+    height: f32,
+    value: f32,
+    max: f32,
+    color: Color,
+) -> f32 {
+    let segment_width = 14.0;
+    let segment_gap = 2.0;
+    let segments = (max / BAR_SEGMENT_SIZE).ceil() as i32;
+    for segment in 0..segments {
+        let x = position.x + segment as f32 * (segment_width + segment_gap);
+        let segment_start = segment as f32 * BAR_SEGMENT_SIZE;
+        // The last segment may hold less than a full segment's worth
+        let capacity = BAR_SEGMENT_SIZE.min(max - segment_start);
+        let fill = ((value - segment_start) / capacity).clamp(0.0, 1.0);
+        let outline = Rectangle::new(x, position.y, segment_width, height);
+
+        d.draw_rectangle_rec(outline, Color::BLACK.fade(0.5));
+        if fill > 0.0 {
+            d.draw_rectangle_rec(
+                Rectangle::new(x, position.y, segment_width * fill, height),
+                color,
+            );
+        }
+        d.draw_rectangle_lines_ex(outline, 1.0, color.fade(0.6));
+    }
+    segments as f32 * (segment_width + segment_gap)
+}
+
 fn render_weapon_pickups(d: &mut RaylibMode2D<RaylibDrawHandle>, pickups: &AllWeaponPickups) {
     let font_size = 16;
     for pickup in &pickups.pickups {
-        let text = pickup.weapon.get_display_name();
+        let text = pickup.item.get_display_name();
         let x = pickup.position.x as i32;
         let y = pickup.position.y as i32;
         d.draw_text(text, x, y, font_size, Color::WHITE);
     }
 }
 
-fn render_weapon_slots(d: &mut RaylibDrawHandle, player: &Player) {
+fn render_drops(d: &mut RaylibMode2D<RaylibDrawHandle>, drops: &AllDrops) {
+    let font_size = 16;
+    for drop in &drops.drops {
+        let center = Vector2::new(drop.position.x, drop.position.y);
+        let size = drop.radius * 1.5;
+        let text_color = match drop.kind {
+            DropKind::RewardChest => {
+                let chest =
+                    Rectangle::new(center.x - size / 2.0, center.y - size / 2.0, size, size);
+                d.draw_rectangle_rec(chest, Color::GOLD);
+                d.draw_rectangle_lines_ex(chest, 3.0, Color::BROWN);
+                Color::GOLD
+            }
+            DropKind::HealthOrb => {
+                d.draw_circle_v(center, size / 3.0, Color::GREEN.fade(0.8));
+                d.draw_circle_lines_v(center, size / 3.0, Color::LIME);
+                Color::LIME
+            }
+            DropKind::EmperorsBlessing => {
+                d.draw_poly(center, 8, size / 2.5, 22.5, Color::WHITE.fade(0.9));
+                d.draw_poly_lines(center, 8, size / 2.5, 22.5, Color::GOLD);
+                Color::GOLD
+            }
+            DropKind::Vacuum => {
+                d.draw_ring(
+                    center,
+                    size / 5.0,
+                    size / 3.0,
+                    0.0,
+                    360.0,
+                    24,
+                    Color::PURPLE,
+                );
+                d.draw_circle_v(center, size / 8.0, Color::VIOLET);
+                Color::VIOLET
+            }
+        };
+
+        let text = drop.kind.get_display_name();
+        let text_width = d.measure_text(text, font_size);
+        d.draw_text(
+            text,
+            center.x as i32 - text_width / 2,
+            (center.y + size / 2.0) as i32 + 4,
+            font_size,
+            text_color,
+        );
+    }
+}
+
+/// Name of the last scripted spawn event, fading out in the middle of the screen.
+fn render_announcement(d: &mut RaylibDrawHandle, enemies: &AllEnemies) {
+    let Some(announcement) = &enemies.director.announcement else {
+        return;
+    };
+    let font_size = 48;
+    let alpha = announcement.remaining.clamp(0.0, 1.0);
+    let text_width = d.measure_text(&announcement.text, font_size);
+    d.draw_text(
+        &announcement.text,
+        d.get_screen_width() / 2 - text_width / 2,
+        d.get_screen_height() / 4,
+        font_size,
+        Color::RED.fade(alpha),
+    );
+}
+
+/// Big bar across the top of the screen while a boss is alive.
+fn render_boss_health_bar(d: &mut RaylibDrawHandle, enemies: &AllEnemies) {
+    let Some(enemy) = enemies.get_boss() else {
+        return;
+    };
+    let Some(boss) = &enemy.boss else {
+        return;
+    };
+
+    let width = 800;
+    let height = 24;
+    let x = d.get_screen_width() / 2 - width / 2;
+    let y = 40;
+    let fraction = (enemy.health as f32 / enemy.max_health as f32).clamp(0.0, 1.0);
+
+    d.draw_rectangle(x, y, width, height, Color::new(40, 0, 0, 200));
+    d.draw_rectangle(x, y, (width as f32 * fraction) as i32, height, Color::RED);
+    // Tick marks where the next phases kick in
+    for phase in boss.phases.iter().skip(1) {
+        let tick_x = x + (width as f32 * phase.health_threshold) as i32;
+        d.draw_line(tick_x, y, tick_x, y + height, Color::WHITE);
+    }
+    d.draw_rectangle_lines(x, y, width, height, Color::WHITE);
+
+    let label = format!(
+        "{} - Phase {}/{}",
+        boss.name,
+        boss.phase_index + 1,
+        boss.phases.len()
+    );
+    d.draw_text(&label, x, y + height + 4, 20, Color::WHITE);
+}
+
+/// Each action with its bindings. The selected row is replaced by a prompt
+/// while waiting for a new key.
+fn render_controls_screen(
+    d: &mut RaylibDrawHandle,
+    controls: &ControlsScreen,
+    input_map: &InputMap,
+) {
+    let screen_width = d.get_screen_width();
+    let font_size = 24;
+    let row_height = font_size + 8;
+    let name_x = screen_width / 2 - 400;
+    let binding_x = screen_width / 2;
+    let mut y = 120;
+
+    d.draw_text("CONTROLS", name_x, y, 48, Color::WHITE);
+    y += 60;
+    d.draw_text(
+        &format!(
+            "{} to rebind, {} to go back",
+            input_map.get_label(Action::MenuConfirm),
+            input_map.get_label(Action::MenuBack)
+        ),
+        name_x,
+        y,
+        18,
+        Color::LIGHTGRAY,
+    );
+    y += 40;
+
+    for (i, row) in get_controls_rows().iter().enumerate() {
+        let selected = i == controls.selected;
+        let color = if selected {
+            Color::GOLD
+        } else {
+            Color::LIGHTGRAY
+        };
+        let label = match row {
+            ControlsRow::Action(action) => action.get_display_name(),
+            ControlsRow::ResetDefaults => "Reset Defaults",
+            ControlsRow::Back => "Back",
+        };
+        let prefix = if selected { "> " } else { "  " };
+        d.draw_text(&format!("{}{}", prefix, label), name_x, y, font_size, color);
+
+        if let ControlsRow::Action(action) = row {
+            let bindings = if selected && controls.waiting_for_binding {
+                "Press a key or button... (Esc to cancel)".to_string()
+            } else {
+                let names: Vec<&str> = input_map
+                    .get_bindings(*action)
+                    .iter()
+                    .map(|binding| binding.get_name())
+                    .collect();
+                names.join(", ")
+            };
+            d.draw_text(&bindings, binding_x, y, font_size, color);
+        }
+        y += row_height;
+    }
+
+    if let Some(error) = &controls.error {
+        d.draw_text(error, name_x, y + 20, 18, Color::RED);
+    }
+}
+
+fn render_weapon_slots(d: &mut RaylibDrawHandle, player: &Player, input_map: &InputMap) {
     let screen_height = d.get_screen_height();
     let slot_size = 160;
     let slot_gap = 5;
@@ -189,6 +708,16 @@ fn render_weapon_slots(d: &mut RaylibDrawHandle, player: &Player) {
     let base_y = screen_height - slot_size - margin;
     let weapon_slots = player.get_weapon_slots();
 
+    let fire_mode = format!(
+        "Fire: {} ({})",
+        if player.manual_fire { "Manual" } else { "Auto" },
+        input_map.get_label(Action::ToggleFireMode)
+    );
+    d.draw_text(&fire_mode, margin, base_y - 20, 14, Color::WHITE);
+    let dash_x = margin + d.measure_text(&fire_mode, 14).max(120) + 20;
+    let dash_label = format!("Dash ({})", input_map.get_label(Action::Dash));
+    render_dash_charges(d, player, &dash_label, dash_x, base_y - 20);
+
     for (i, slot) in weapon_slots.iter().enumerate() {
         let x = margin + (i as i32) * (slot_size + slot_gap);
         let y = base_y;
@@ -202,6 +731,122 @@ fn render_weapon_slots(d: &mut RaylibDrawHandle, player: &Player) {
         let text_x = x + 5;
         let text_y = y + (slot_size - font_size) / 2;
         d.draw_text(text, text_x, text_y, font_size, Color::WHITE);
+
+        let magazine = player.weapons[i]
+            .as_ref()
+            .and_then(|weapon| weapon.get_magazine());
+        if let Some(magazine) = magazine {
+            render_magazine(d, magazine, x + 5, y + slot_size - 14, slot_size - 10);
+        }
+    }
+
+    let abilities_x = margin + weapon_slots.len() as i32 * (slot_size + slot_gap) + 20;
+    render_abilities(d, player, input_map, abilities_x, base_y + slot_size);
+}
+
+/// A box per ability with its key, shaded and counting down while on cooldown.
+/// `bottom` lines the boxes up with the bottom of the weapon slots.
+fn render_abilities(
+    d: &mut RaylibDrawHandle,
+    player: &Player,
+    input_map: &InputMap,
+    x: i32,
+    bottom: i32,
+) {
+    let box_size = 100;
+    let box_gap = 10;
+    let box_y = bottom - box_size;
+    let actions = [Action::Ability1, Action::Ability2];
+    for (i, (ability, action)) in player.abilities.iter().zip(actions).enumerate() {
+        let box_x = x + i as i32 * (box_size + box_gap);
+
+        d.draw_text(
+            ability.kind.get_display_name(),
+            box_x + 5,
+            box_y + 5,
+            12,
+            Color::WHITE,
+        );
+        d.draw_text(
+            &input_map.get_label(action),
+            box_x + 5,
+            box_y + box_size - 20,
+            14,
+            Color::GOLD,
+        );
+        if ability.rank > 0 {
+            let rank = format!("+{}", ability.rank);
+            let rank_x = box_x + box_size - 25;
+            d.draw_text(&rank, rank_x, box_y + box_size - 20, 14, Color::SKYBLUE);
+        }
+
+        if !ability.is_ready() {
+            // Shade drains downwards as the cooldown runs out
+            let shaded = (box_size as f32 * (1.0 - ability.get_cooldown_progress())) as i32;
+            d.draw_rectangle(
+                box_x,
+                box_y + box_size - shaded,
+                box_size,
+                shaded,
+                Color::BLACK.fade(0.6),
+            );
+            let seconds = format!("{:.0}", ability.cooldown_remaining.ceil());
+            let center_x = box_x + box_size / 2 - 8;
+            d.draw_text(
+                &seconds,
+                center_x,
+                box_y + box_size / 2 - 12,
+                24,
+                Color::WHITE,
+            );
+        }
+        let outline = if ability.is_ready() {
+            Color::GOLD
+        } else {
+            Color::GRAY
+        };
+        d.draw_rectangle_lines(box_x, box_y, box_size, box_size, outline);
+    }
+}
+
+/// One pip per dash charge, with the next one filling up as it recharges.
+fn render_dash_charges(d: &mut RaylibDrawHandle, player: &Player, label: &str, x: i32, y: i32) {
+    let dash = &player.dash;
+    d.draw_text(label, x, y, 14, Color::WHITE);
+    let pip_size = 12;
+    let pip_gap = 4;
+    let pips_x = x + d.measure_text(label, 14).max(80) + 20;
+    for charge in 0..dash.max_charges {
+        let pip_x = pips_x + charge * (pip_size + pip_gap);
+        if charge < dash.charges {
+            d.draw_rectangle(pip_x, y, pip_size, pip_size, Color::SKYBLUE);
+        } else if charge == dash.charges {
+            let filled = (pip_size as f32 * dash.get_recharge_progress()) as i32;
+            d.draw_rectangle(pip_x, y + pip_size - filled, pip_size, filled, Color::GRAY);
+        }
+        d.draw_rectangle_lines(pip_x, y, pip_size, pip_size, Color::WHITE);
+    }
+}
+
+/// Ammo pips, or a reload bar while the magazine is empty.
+fn render_magazine(d: &mut RaylibDrawHandle, magazine: &Magazine, x: i32, y: i32, width: i32) {
+    let pip_size = 8;
+    let pip_gap = 4;
+
+    if magazine.is_reloading() {
+        let fill_width = (width as f32 * magazine.get_reload_progress()) as i32;
+        d.draw_rectangle_lines(x, y, width, pip_size, Color::GRAY);
+        d.draw_rectangle(x, y, fill_width, pip_size, Color::ORANGE);
+        return;
+    }
+
+    for pip in 0..magazine.size {
+        let pip_x = x + pip as i32 * (pip_size + pip_gap);
+        if pip < magazine.rounds {
+            d.draw_rectangle(pip_x, y, pip_size, pip_size, Color::GOLD);
+        } else {
+            d.draw_rectangle_lines(pip_x, y, pip_size, pip_size, Color::GRAY);
+        }
     }
 }
 
@@ -290,112 +935,186 @@ pub fn render_enemies(d: &mut RaylibMode2D<RaylibDrawHandle>, enemies: &AllEnemi
         };
 
         let source_rec = Rectangle::new(0.0, 0.0, source_width, texture.height as f32);
-        let dest_rec = Rectangle::new(
-            enemy.position.x,
-            enemy.position.y,
-            texture.width as f32,
-            texture.height as f32,
-        );
-        let origin = Vector2::new(texture.width as f32 / 2.0, texture.height as f32 / 2.0);
-        d.draw_texture_pro(&texture, source_rec, dest_rec, origin, 0.0, Color::WHITE);
+        let width = texture.width as f32 * enemy.scale;
+        let height = texture.height as f32 * enemy.scale;
+        let dest_rec = Rectangle::new(enemy.position.x, enemy.position.y, width, height);
+        let origin = Vector2::new(width / 2.0, height / 2.0);
+        let tint = match enemy.behaviour.get_fuse_progress() {
+            // Blink faster and faster as the fuse burns down
+            Some(progress) if (progress * progress * 20.0) as i32 % 2 == 0 => Color::WHITE,
+            Some(_) => Color::RED,
+            None if enemy.enemy_type == EnemyType::Exploder => Color::new(255, 120, 120, 255),
+            None if enemy.enemy_type == EnemyType::Boss => Color::new(200, 140, 255, 255),
+            // Each split shifts the alien a little further towards green
+            None if enemy.enemy_type == EnemyType::Splitter => {
+                let shade = (255.0 * enemy.scale) as u8;
+                Color::new(shade, 255, shade, 255)
+            }
+            None => Color::WHITE,
+        };
+        d.draw_texture_pro(&texture, source_rec, dest_rec, origin, 0.0, tint);
+
+        if let Some(elite) = &enemy.elite {
+            // One ring per affix so elites read at a glance without new sprites
+            let center = Vector2::new(enemy.position.x, enemy.position.y);
+            let base_radius = width.max(height) / 2.0 + 4.0;
+            for (index, affix) in elite.affixes.iter().enumerate() {
+                let radius = base_radius + index as f32 * 4.0;
+                d.draw_circle_lines_v(center, radius, affix.get_color());
+            }
+            if elite.shield > 0 {
+                let fraction = elite.shield as f32 / elite.max_shield as f32;
+                let bar = Rectangle::new(
+                    enemy.position.x - width / 2.0,
+                    enemy.position.y - base_radius - 10.0,
+                    width * fraction,
+                    4.0,
+                );
+                d.draw_rectangle_rec(bar, Color::SKYBLUE);
+            }
+        }
+
+        if let Some(ranged_attack) = &enemy.ranged_attack {
+            if let Some(progress) = ranged_attack.get_telegraph_progress() {
+                let length = ranged_attack.preferred_distance * 1.5;
+                render_telegraph(d, enemy.position, ranged_attack.aim_angle, length, progress);
+            }
+        }
+        if let Some((angle, progress)) = enemy.behaviour.get_charge_telegraph() {
+            render_telegraph(d, enemy.position, angle, CHARGE_TELEGRAPH_LENGTH, progress);
+        }
 
         if game_state::DEBUG_MODE {
-            let debug_rect = Rectangle::new(
-                enemy.position.x - origin.x,
-                enemy.position.y - origin.y,
-                texture.width as f32,
-                texture.height as f32,
-            );
+            let debug_rect = enemy.get_collision_rect(texture);
             d.draw_rectangle_lines_ex(debug_rect, 2.0, Color::RED);
         }
     }
 }
 
+fn render_explosions(d: &mut RaylibMode2D<RaylibDrawHandle>, enemies: &AllEnemies) {
+    for explosion in &enemies.explosions {
+        let fade = 1.0 - (explosion.age / EXPLOSION_FADE_TIME).clamp(0.0, 1.0);
+        let center = Vector2::new(explosion.position.x, explosion.position.y);
+        // Expands slightly as it fades
+        let radius = explosion.radius * (0.8 + 0.2 * (1.0 - fade));
+        d.draw_circle_v(center, radius, Color::ORANGE.fade(fade * 0.5));
+        d.draw_circle_lines_v(center, radius, Color::YELLOW.fade(fade));
+    }
+}
+
+/// Aim line that brightens as an enemy winds up a shot or charge.
+fn render_telegraph(
+    d: &mut RaylibMode2D<RaylibDrawHandle>,
+    position: Position,
+    angle: f32,
+    length: f32,
+    progress: f32,
+) {
+    let start = Vector2::new(position.x, position.y);
+    let end = Vector2::new(
+        start.x + angle.cos() * length,
+        start.y + angle.sin() * length,
+    );
+    let color = Color::new(255, 40, 40, 255).fade(0.2 + progress * 0.6);
+    d.draw_line_ex(start, end, 1.0 + progress * 3.0, color);
+}
+
+fn render_hazard_zones(d: &mut RaylibMode2D<RaylibDrawHandle>, projectiles: &AllProjectiles) {
+    for projectile in &projectiles.projectiles {
+        let Visual::Zone { fill, edge } = projectile.visual else {
+            continue;
+        };
+        render_hazard_zone(d, projectile, fill, edge);
+    }
+}
+
+fn render_hazard_zone(
+    d: &mut RaylibMode2D<RaylibDrawHandle>,
+    zone_data: &Projectile,
+    fill_color: Color,
+    edge_color: Color,
+) {
+    // Fade out over the last part of the zone's life
+    let fade = ((1.0 - zone_data.get_progress()) * 3.0).min(1.0);
+    let center = Vector2::new(zone_data.position.x, zone_data.position.y);
+    let radius = zone_data.shape.get_width(0.0) / 2.0;
+
+    d.draw_circle_v(
+        center,
+        radius,
+        fill_color.fade(fade * fill_color.a as f32 / 255.0),
+    );
+    d.draw_circle_lines_v(
+        center,
+        radius,
+        edge_color.fade(fade * edge_color.a as f32 / 255.0),
+    );
+}
+
 fn render_projectiles(
     d: &mut RaylibMode2D<RaylibDrawHandle>,
-    projectiles: &AllProjectiles,
+    active_projectiles: &[Projectile],
+    bullet_texture: &Texture2D,
     white_texture: &Texture2D,
     multi_melta_shader: &mut MultiMeltaShader,
     elapsed_time: f32,
 ) {
-    let active_projectiles = &projectiles.projectiles;
     for projetile in active_projectiles {
-        match projetile {
-            Projectile::Bolter(bolter_data) => {
+        match projetile.visual {
+            Visual::Bullet => {
                 let source_rec = Rectangle::new(
                     0.0,
                     0.0,
-                    projectiles.texture.width as f32,
-                    projectiles.texture.height as f32,
+                    bullet_texture.width as f32,
+                    bullet_texture.height as f32,
                 );
                 let dest_rec = Rectangle::new(
-                    bolter_data.position.x,
-                    bolter_data.position.y,
-                    projectiles.texture.width as f32 / 2.0,
-                    projectiles.texture.height as f32 / 2.0,
+                    projetile.position.x,
+                    projetile.position.y,
+                    bullet_texture.width as f32 / 2.0,
+                    bullet_texture.height as f32 / 2.0,
                 );
                 let origin = Vector2::new(
-                    projectiles.texture.width as f32 / 4.0,
-                    projectiles.texture.height as f32 / 4.0,
+                    bullet_texture.width as f32 / 4.0,
+                    bullet_texture.height as f32 / 4.0,
                 );
 
-                let rotation = bolter_data.angle.to_degrees();
+                let rotation = projetile.angle.to_degrees();
                 d.draw_texture_pro(
-                    &projectiles.texture,
+                    bullet_texture,
                     source_rec,
                     dest_rec,
                     origin,
                     rotation,
                     Color::WHITE,
                 );
-                if game_state::DEBUG_MODE {
-                    if game_state::DEBUG_MODE {
-                        let debug_rect = Rectangle::new(
-                            bolter_data.position.x - origin.x,
-                            bolter_data.position.y - origin.y,
-                            projectiles.texture.width as f32 / 2.0,
-                            projectiles.texture.height as f32 / 2.0,
-                        );
-                        d.draw_rectangle_lines_ex(debug_rect, 2.0, Color::RED);
-                    }
-                }
             }
-            Projectile::PowerSword(sword_data) => {
-                let slash_offset = sword_data.get_slash_offset();
-                let rotation = 0.0;
-
-                let (offset_x, offset_y) = (0.0, slash_offset);
-
-                let origin = match sword_data.direction {
-                    Direction::Left => Vector2::new(sword_data.width, sword_data.height / 2.0),
-                    _ => Vector2::new(0.0, sword_data.height / 2.0),
+            Visual::Blade { color } => {
+                let progress = projetile.get_progress();
+                let base =
+                    projetile
+                        .shape
+                        .get_blade_base(&projetile.position, projetile.angle, progress);
+                let Shape::Blade {
+                    length, thickness, ..
+                } = projetile.shape
+                else {
+                    continue;
                 };
-                let dest_rec = Rectangle::new(
-                    sword_data.position.x + offset_x,
-                    sword_data.position.y + offset_y,
-                    sword_data.width,
-                    sword_data.height,
-                );
-
-                d.draw_rectangle_pro(dest_rec, origin, rotation, Color::BLUE);
 
-                if game_state::DEBUG_MODE {
-                    let debug_rect = Rectangle::new(
-                        sword_data.position.x + offset_x - origin.x,
-                        sword_data.position.y + offset_y - origin.y,
-                        sword_data.width,
-                        sword_data.height,
-                    );
-                    d.draw_rectangle_lines_ex(debug_rect, 2.0, Color::RED);
-                }
+                let dest_rec = Rectangle::new(base.x, base.y, length, thickness);
+                let origin = Vector2::new(0.0, thickness / 2.0);
+                d.draw_rectangle_pro(dest_rec, origin, projetile.angle.to_degrees(), color);
             }
-            Projectile::MultiMelta(melta_data) => {
-                let rotation = melta_data.angle.to_degrees();
-                let width = melta_data.current_width();
-                let length = melta_data.length;
+            Visual::Flame => {
+                let rotation = projetile.angle.to_degrees();
+                let width = projetile.shape.get_width(projetile.get_progress());
+                let Shape::Flame { length, .. } = projetile.shape else {
+                    continue;
+                };
 
                 let dest_rec =
-                    Rectangle::new(melta_data.position.x, melta_data.position.y, length, width);
+                    Rectangle::new(projetile.position.x, projetile.position.y, length, width);
                 let origin = Vector2::new(length / 2.0, width / 2.0);
 
                 let noise_scale = 6.0_f32;
@@ -443,31 +1162,52 @@ fn render_projectiles(
                     rotation,
                     Color::WHITE,
                 );
-
-                if game_state::DEBUG_MODE {
-                    let debug_rect = Rectangle::new(
-                        melta_data.position.x - origin.x,
-                        melta_data.position.y - origin.y,
-                        length,
-                        width,
-                    );
-                    shader_mode.draw_rectangle_lines_ex(debug_rect, 2.0, Color::RED);
+            }
+            // Drawn on the ground layer by render_hazard_zones
+            Visual::Zone { .. } => {}
+            Visual::Blast { color } => {
+                let progress = projetile.get_progress();
+                let center = Vector2::new(projetile.position.x, projetile.position.y);
+                let radius = projetile.shape.get_width(0.0) / 2.0 * (0.5 + progress * 0.5);
+                let fade = 1.0 - progress;
+                d.draw_circle_v(center, radius, color.fade(0.6 * fade));
+                d.draw_circle_v(center, radius * 0.5, Color::YELLOW.fade(0.8 * fade));
+            }
+            Visual::StrikeMarker { delay } => {
+                let center = Vector2::new(projetile.position.x, projetile.position.y);
+                let radius = projetile.shape.get_width(0.0) / 2.0;
+                if projetile.age < delay {
+                    // Inner circle grows to meet the ring as the strike lands
+                    let charge = projetile.age / delay;
+                    d.draw_circle_lines_v(center, radius, Color::RED);
+                    d.draw_circle_v(center, radius * charge, Color::RED.fade(0.25));
+                } else {
+                    let beam_top = Vector2::new(center.x, center.y - 2000.0);
+                    d.draw_line_ex(beam_top, center, radius * 0.8, Color::SKYBLUE.fade(0.7));
+                    d.draw_line_ex(beam_top, center, radius * 0.3, Color::WHITE);
+                    d.draw_circle_v(center, radius, Color::SKYBLUE.fade(0.5));
                 }
             }
-            Projectile::Shotgun(shotgun_data) => {
-                let rotation = shotgun_data.angle.to_degrees();
-                let origin = Vector2::new(shotgun_data.width / 2.0, shotgun_data.height / 2.0);
-                let dest_rec = Rectangle::new(
-                    shotgun_data.position.x,
-                    shotgun_data.position.y,
-                    shotgun_data.width,
-                    shotgun_data.height,
-                );
+            Visual::Orb { color } => {
+                let center = Vector2::new(projetile.position.x, projetile.position.y);
+                let radius = projetile.shape.get_width(0.0) / 2.0;
+                d.draw_circle_v(center, radius * 1.8, color.fade(0.25));
+                d.draw_circle_v(center, radius, color);
+                d.draw_circle_v(center, radius * 0.5, Color::WHITE);
+            }
+            Visual::Tracer { tail_length } => {
+                let Shape::Rect { width, height } = projetile.shape else {
+                    continue;
+                };
+                let rotation = projetile.angle.to_degrees();
+                let origin = Vector2::new(width / 2.0, height / 2.0);
+                let dest_rec =
+                    Rectangle::new(projetile.position.x, projetile.position.y, width, height);
 
-                let tail_start = Vector2::new(shotgun_data.position.x, shotgun_data.position.y);
+                let tail_start = Vector2::new(projetile.position.x, projetile.position.y);
                 let tail_offset = Vector2::new(
-                    shotgun_data.angle.cos() * shotgun_data.tail_length,
-                    shotgun_data.angle.sin() * shotgun_data.tail_length,
+                    projetile.angle.cos() * tail_length,
+                    projetile.angle.sin() * tail_length,
                 );
 
                 let tail_color_primary = Color::new(255, 235, 0, 140);
@@ -506,18 +1246,35 @@ fn render_projectiles(
                 );
 
                 d.draw_rectangle_pro(dest_rec, origin, rotation, Color::new(255, 255, 40, 255));
-
-                if game_state::DEBUG_MODE {
-                    let debug_rect = Rectangle::new(
-                        shotgun_data.position.x - origin.x,
-                        shotgun_data.position.y - origin.y,
-                        shotgun_data.width,
-                        shotgun_data.height,
-                    );
-                    d.draw_rectangle_lines_ex(debug_rect, 2.0, Color::RED);
-                }
             }
         }
+
+        if game_state::DEBUG_MODE {
+            render_projectile_debug(d, projetile);
+        }
+    }
+}
+
+/// Outlines the area a projectile actually collides with.
+fn render_projectile_debug(d: &mut RaylibMode2D<RaylibDrawHandle>, projectile: &Projectile) {
+    if let Shape::Rect { width, height } = projectile.shape {
+        let debug_rect = Rectangle::new(
+            projectile.position.x - width / 2.0,
+            projectile.position.y - height / 2.0,
+            width,
+            height,
+        );
+        d.draw_rectangle_lines_ex(debug_rect, 2.0, Color::RED);
+        return;
+    }
+
+    let circles = projectile.shape.collision_circles(
+        &projectile.position,
+        projectile.angle,
+        projectile.get_progress(),
+    );
+    for (center, radius) in circles {
+        d.draw_circle_lines_v(center, radius, Color::RED);
     }
 }
 
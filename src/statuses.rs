@@ -6,6 +6,7 @@ pub enum Status {
     Stun(StunStatus),
     Regeneration(RegenerationStatus),
     SpeedBoost(SpeedBoostStatus),
+    DamageBoost(DamageBoostStatus),
 }
 
 impl Status {
@@ -21,6 +22,72 @@ impl Status {
             Status::Stun(data) => data.remaining_duration,
             Status::Regeneration(data) => data.remaining_duration,
             Status::SpeedBoost(data) => data.remaining_duration,
+            Status::DamageBoost(data) => data.remaining_duration,
+        }
+    }
+
+    pub fn is_same_type(&self, other: &Status) -> bool {
+        matches!(
+            (self, other),
+            (Status::Poison(_), Status::Poison(_))
+                | (Status::Burn(_), Status::Burn(_))
+                | (Status::Slow(_), Status::Slow(_))
+                | (Status::Stun(_), Status::Stun(_))
+                | (Status::Regeneration(_), Status::Regeneration(_))
+                | (Status::SpeedBoost(_), Status::SpeedBoost(_))
+                | (Status::DamageBoost(_), Status::DamageBoost(_))
+        )
+    }
+
+    /// Advances the status by `delta` and returns the health change it caused this frame.
+    pub fn tick(&mut self, delta: f32) -> i32 {
+        match self {
+            Status::Poison(data) => {
+                data.time_since_last_tick += delta;
+                data.remaining_duration -= delta;
+
+                if data.time_since_last_tick >= data.tick_interval {
+                    data.time_since_last_tick = 0.0;
+                    return -data.damage_per_tick;
+                }
+                0
+            }
+            Status::Burn(data) => {
+                data.time_since_last_tick += delta;
+                data.remaining_duration -= delta;
+
+                if data.time_since_last_tick >= data.tick_interval {
+                    data.time_since_last_tick = 0.0;
+                    return -data.damage_per_tick;
+                }
+                0
+            }
+            Status::Slow(data) => {
+                data.remaining_duration -= delta;
+                0
+            }
+            Status::Stun(data) => {
+                data.remaining_duration -= delta;
+                0
+            }
+            Status::Regeneration(data) => {
+                data.time_since_last_tick += delta;
+                data.remaining_duration -= delta;
+
+                if data.time_since_last_tick >= data.tick_interval {
+                    data.time_since_last_tick = 0.0;
+                    return data.heal_per_tick;
+                }
+                0
+            }
+            Status::SpeedBoost(data) => {
+                data.remaining_duration -= delta;
+                0
+            }
+            Status::DamageBoost(data) => {
+                data.remaining_duration -= delta;
+                0
+            }
         }
     }
 
@@ -32,10 +99,44 @@ impl Status {
             Status::Stun(_) => "Stun",
             Status::Regeneration(_) => "Regeneration",
             Status::SpeedBoost(_) => "Speed Boost",
+            Status::DamageBoost(_) => "Damage Boost",
         }
     }
 }
 
+/// Combined movement multiplier of a set of statuses. Stun always wins.
+pub fn calculate_speed_multiplier(statuses: &[Status]) -> f32 {
+    let mut multiplier = 1.0;
+
+    for status in statuses {
+        match status {
+            Status::Stun(_) => return 0.0, // Stun overrides everything
+            Status::Slow(data) => multiplier *= data.speed_multiplier,
+            Status::SpeedBoost(data) => multiplier *= data.speed_multiplier,
+            _ => {}
+        }
+    }
+
+    multiplier
+}
+
+/// Combined damage multiplier for everything the owner fires.
+pub fn calculate_damage_multiplier(statuses: &[Status]) -> f32 {
+    statuses
+        .iter()
+        .map(|status| match status {
+            Status::DamageBoost(data) => data.damage_multiplier,
+            _ => 1.0,
+        })
+        .product()
+}
+
+/// Replaces any status of the same type (single instance rule) and adds the new one.
+pub fn add_status(statuses: &mut Vec<Status>, status: Status) {
+    statuses.retain(|s| !s.is_same_type(&status));
+    statuses.push(status);
+}
+
 #[derive(Clone, Copy)]
 pub struct PoisonStatus {
     pub damage_per_tick: i32,
@@ -76,3 +177,9 @@ pub struct SpeedBoostStatus {
     pub speed_multiplier: f32,
     pub remaining_duration: f32,
 }
+
+#[derive(Clone, Copy)]
+pub struct DamageBoostStatus {
+    pub damage_multiplier: f32,
+    pub remaining_duration: f32,
+}
@@ -0,0 +1,93 @@
+use crate::{
+    abilities::MAX_ABILITY_RANK,
+    player::{Player, MAX_DASH_CHARGES},
+};
+
+/// Most times a single ballistic round may bounce.
+pub const MAX_RICOCHET_BOUNCES: i32 = 3;
+pub const SPEED_LOADER_BONUS: f32 = 0.25;
+pub const MAX_RELOAD_SPEED: f32 = 2.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Upgrade {
+    /// Ballistic rounds bounce to another enemy (or off the edge of the play area)
+    /// once more after a hit.
+    RicochetRounds,
+    /// Magazines refill faster.
+    SpeedLoaders,
+    /// One more dash before having to wait for a recharge.
+    JumpPackCells,
+    /// Every ability recharges faster and hits harder (or lasts longer).
+    PuritySeals,
+}
+
+/// Every upgrade that can show up as a pickup.
+pub const ALL_UPGRADES: [Upgrade; 4] = [
+    Upgrade::RicochetRounds,
+    Upgrade::SpeedLoaders,
+    Upgrade::JumpPackCells,
+    Upgrade::PuritySeals,
+];
+
+impl Upgrade {
+    pub fn get_display_name(&self) -> &str {
+        match self {
+            Upgrade::RicochetRounds => "Ricochet Rounds",
+            Upgrade::SpeedLoaders => "Speed Loaders",
+            Upgrade::JumpPackCells => "Jump Pack Cells",
+            Upgrade::PuritySeals => "Purity Seals",
+        }
+    }
+
+    /// Whether picking this upgrade up would still change anything for the player.
+    pub fn can_apply(&self, player: &Player) -> bool {
+        match self {
+            Upgrade::RicochetRounds => player.ricochet_bounces < MAX_RICOCHET_BOUNCES,
+            Upgrade::SpeedLoaders => player.reload_speed < MAX_RELOAD_SPEED,
+            Upgrade::JumpPackCells => player.dash.max_charges < MAX_DASH_CHARGES,
+            Upgrade::PuritySeals => player
+                .abilities
+                .iter()
+                .any(|ability| ability.rank < MAX_ABILITY_RANK),
+        }
+    }
+
+    pub fn apply(&self, player: &mut Player) {
+        match self {
+            Upgrade::RicochetRounds => {
+                player.ricochet_bounces = (player.ricochet_bounces + 1).min(MAX_RICOCHET_BOUNCES);
+            }
+            Upgrade::SpeedLoaders => {
+                player.reload_speed =
+                    (player.reload_speed + SPEED_LOADER_BONUS).min(MAX_RELOAD_SPEED);
+            }
+            Upgrade::JumpPackCells => {
+                player.dash.max_charges = (player.dash.max_charges + 1).min(MAX_DASH_CHARGES);
+                player.dash.charges = (player.dash.charges + 1).min(player.dash.max_charges);
+            }
+            Upgrade::PuritySeals => {
+                for ability in player.abilities.iter_mut() {
+                    ability.rank = (ability.rank + 1).min(MAX_ABILITY_RANK);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemy::tests::create_test_player;
+
+    #[test]
+    fn test_jump_pack_cells_never_overfill_charges() {
+        let mut player = create_test_player(0.0, 0.0);
+        player.dash.max_charges = MAX_DASH_CHARGES;
+        player.dash.charges = MAX_DASH_CHARGES;
+
+        Upgrade::JumpPackCells.apply(&mut player);
+
+        assert_eq!(player.dash.max_charges, MAX_DASH_CHARGES);
+        assert_eq!(player.dash.charges, MAX_DASH_CHARGES);
+    }
+}
@@ -6,6 +6,24 @@ pub enum Direction {
     Right,
 }
 
+impl Direction {
+    /// Closest of the four directions to a vector, favouring left and right
+    /// on exact diagonals so sprites keep flipping the way they face.
+    pub fn from_vector(x: f32, y: f32) -> Self {
+        if x.abs() >= y.abs() {
+            if x < 0.0 {
+                Direction::Left
+            } else {
+                Direction::Right
+            }
+        } else if y < 0.0 {
+            Direction::Up
+        } else {
+            Direction::Down
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq)]
 pub struct Position {
     pub x: f32,
@@ -1,20 +1,41 @@
 use rand::prelude::*;
 
 use crate::{
+    drops::apply_magnet,
     player::Player,
+    upgrades::{Upgrade, ALL_UPGRADES},
     utils::Position,
-    weapons::{Weapon, WeaponData},
+    weapons::Weapon,
 };
 
 const PICKUP_RADIUS: f32 = 24.0;
 const SPAWN_RADIUS: f32 = 2500.0;
 const TARGET_PICKUP_COUNT: usize = 6;
+const MAX_UPGRADE_PICKUPS: usize = 1; // Upgrades on the map at once
+const UPGRADE_SPAWN_CHANCE: f64 = 0.2;
+
+#[derive(Clone)]
+pub enum PickupItem {
+    Weapon(Weapon),
+    Upgrade(Upgrade),
+}
+
+impl PickupItem {
+    pub fn get_display_name(&self) -> &str {
+        match self {
+            PickupItem::Weapon(weapon) => weapon.get_display_name(),
+            PickupItem::Upgrade(upgrade) => upgrade.get_display_name(),
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct WeaponPickup {
-    pub weapon: Weapon,
+    pub item: PickupItem,
     pub position: Position,
     pub radius: f32,
+    /// Zero until the player's magnet catches it.
+    pub speed: f32,
 }
 
 pub struct AllWeaponPickups {
@@ -24,41 +45,47 @@ pub struct AllWeaponPickups {
 }
 
 impl AllWeaponPickups {
-    pub fn new(player_pos: Position) -> Self {
+    pub fn new(player: &Player) -> Self {
         let mut pickups = Self {
             pickups: vec![],
             spawn_radius: SPAWN_RADIUS,
             target_count: TARGET_PICKUP_COUNT,
         };
-        pickups.spawn_around_player(&player_pos);
+        pickups.spawn_around_player(player);
         pickups
     }
 
-    pub fn update(&mut self, player: &mut Player) {
-        if player.has_full_weapon_slots() {
-            self.pickups.clear();
-            return;
+    pub fn update(&mut self, player: &mut Player, delta: &f32) {
+        for pickup in self.pickups.iter_mut() {
+            apply_magnet(&mut pickup.position, &mut pickup.speed, player, *delta);
         }
-
         self.handle_pickups(player);
 
-        if !player.has_full_weapon_slots() {
-            self.spawn_around_player(&player.position);
-        } else {
-            self.pickups.clear();
-        }
+        // Drop anything the player can no longer make use of
+        let full_weapon_slots = player.has_full_weapon_slots();
+        self.pickups.retain(|pickup| match &pickup.item {
+            PickupItem::Weapon(_) => !full_weapon_slots,
+            PickupItem::Upgrade(upgrade) => upgrade.can_apply(player),
+        });
+
+        self.spawn_around_player(player);
     }
 
     fn handle_pickups(&mut self, player: &mut Player) {
         let mut index = 0;
         while index < self.pickups.len() {
-            if player.has_full_weapon_slots() {
-                break;
-            }
-
             let pickup = self.pickups[index].clone();
             if is_pickup_in_range(player, &pickup) {
-                if player.add_or_stack_weapon(pickup.weapon) {
+                let picked_up = match pickup.item {
+                    PickupItem::Weapon(weapon) => {
+                        !player.has_full_weapon_slots() && player.add_or_stack_weapon(weapon)
+                    }
+                    PickupItem::Upgrade(upgrade) => {
+                        upgrade.apply(player);
+                        true
+                    }
+                };
+                if picked_up {
                     self.pickups.swap_remove(index);
                     continue;
                 }
@@ -68,18 +95,49 @@ impl AllWeaponPickups {
         }
     }
 
-    fn spawn_around_player(&mut self, player_pos: &Position) {
+    fn spawn_around_player(&mut self, player: &Player) {
         let mut rng = rand::rng();
         while self.pickups.len() < self.target_count {
-            let position = random_position_within_radius(player_pos, self.spawn_radius, &mut rng);
-            let weapon = random_weapon(&mut rng);
+            let Some(item) = self.random_item(player, &mut rng) else {
+                break;
+            };
+            let position =
+                random_position_within_radius(&player.position, self.spawn_radius, &mut rng);
             self.pickups.push(WeaponPickup {
-                weapon,
+                item,
                 position,
                 radius: PICKUP_RADIUS,
+                speed: 0.0,
             });
         }
     }
+
+    fn random_item(&self, player: &Player, rng: &mut impl Rng) -> Option<PickupItem> {
+        let upgrade_count = self
+            .pickups
+            .iter()
+            .filter(|pickup| matches!(pickup.item, PickupItem::Upgrade(_)))
+            .count();
+        let available_upgrades: Vec<Upgrade> = ALL_UPGRADES
+            .iter()
+            .filter(|upgrade| upgrade.can_apply(player))
+            .copied()
+            .collect();
+        let can_offer_upgrade =
+            upgrade_count < MAX_UPGRADE_PICKUPS && !available_upgrades.is_empty();
+
+        if can_offer_upgrade
+            && (player.has_full_weapon_slots() || rng.random_bool(UPGRADE_SPAWN_CHANCE))
+        {
+            let upgrade = available_upgrades[rng.random_range(0..available_upgrades.len())];
+            return Some(PickupItem::Upgrade(upgrade));
+        }
+
+        if player.has_full_weapon_slots() {
+            return None;
+        }
+        Some(PickupItem::Weapon(random_weapon(rng)))
+    }
 }
 
 fn random_position_within_radius(
@@ -97,35 +155,13 @@ fn random_position_within_radius(
 }
 
 fn random_weapon(rng: &mut impl Rng) -> Weapon {
-    match rng.random_range(0..4) {
-        0 => Weapon::Bolter(WeaponData {
-            damage: 10.0,
-            tick_interval: 1.0,
-            time_since_last_tick: 0.0,
-            stack_count: 1,
-            queued_shots: vec![],
-        }),
-        1 => Weapon::PowerSword(WeaponData {
-            damage: 24.0,
-            tick_interval: 0.6,
-            time_since_last_tick: 0.0,
-            stack_count: 1,
-            queued_shots: vec![],
-        }),
-        2 => Weapon::Shotgun(WeaponData {
-            damage: 8.0,
-            tick_interval: 1.2,
-            time_since_last_tick: 0.0,
-            stack_count: 1,
-            queued_shots: vec![],
-        }),
-        _ => Weapon::MultiMelta(WeaponData {
-            damage: 18.0,
-            tick_interval: 1.8,
-            time_since_last_tick: 0.0,
-            stack_count: 1,
-            queued_shots: vec![],
-        }),
+    match rng.random_range(0..6) {
+        0 => Weapon::new_bolter(),
+        1 => Weapon::new_power_sword(),
+        2 => Weapon::new_shotgun(),
+        3 => Weapon::new_gas_grenade(),
+        4 => Weapon::new_consecration(),
+        _ => Weapon::new_multi_melta(),
     }
 }
 
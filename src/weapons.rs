@@ -1,18 +1,61 @@
+pub const BOLTER_MAGAZINE_SIZE: u32 = 8;
+pub const BOLTER_RELOAD_TIME: f32 = 2.0;
+pub const SHOTGUN_MAGAZINE_SIZE: u32 = 2;
+pub const SHOTGUN_RELOAD_TIME: f32 = 1.5;
+
 #[derive(Clone)]
 pub enum Weapon {
     Bolter(WeaponData),
     MultiMelta(WeaponData),
     PowerSword(WeaponData),
     Shotgun(WeaponData),
+    GasGrenade(WeaponData),
+    Consecration(WeaponData),
 }
 
 impl Weapon {
+    pub fn new_bolter() -> Self {
+        Weapon::Bolter(WeaponData::new(
+            10.0,
+            1.0,
+            AimMode::Mouse,
+            Some(Magazine::new(BOLTER_MAGAZINE_SIZE, BOLTER_RELOAD_TIME)),
+        ))
+    }
+
+    pub fn new_power_sword() -> Self {
+        Weapon::PowerSword(WeaponData::new(25.0, 0.6, AimMode::Movement, None))
+    }
+
+    pub fn new_shotgun() -> Self {
+        Weapon::Shotgun(WeaponData::new(
+            10.0,
+            1.2,
+            AimMode::Movement,
+            Some(Magazine::new(SHOTGUN_MAGAZINE_SIZE, SHOTGUN_RELOAD_TIME)),
+        ))
+    }
+
+    pub fn new_gas_grenade() -> Self {
+        Weapon::GasGrenade(WeaponData::new(0.0, 2.5, AimMode::Mouse, None))
+    }
+
+    pub fn new_consecration() -> Self {
+        Weapon::Consecration(WeaponData::new(2.0, 5.0, AimMode::Movement, None))
+    }
+
+    pub fn new_multi_melta() -> Self {
+        Weapon::MultiMelta(WeaponData::new(2.0, 1.8, AimMode::Mouse, None))
+    }
+
     pub fn get_display_name(&self) -> &str {
         match self {
             Weapon::Bolter(_) => "Bolter",
             Weapon::MultiMelta(_) => "Multi Melta",
             Weapon::PowerSword(_) => "Power Sword",
             Weapon::Shotgun(_) => "Shotgun",
+            Weapon::GasGrenade(_) => "Gas Grenade",
+            Weapon::Consecration(_) => "Consecration",
         }
     }
 
@@ -23,6 +66,8 @@ impl Weapon {
                 | (Weapon::MultiMelta(_), Weapon::MultiMelta(_))
                 | (Weapon::PowerSword(_), Weapon::PowerSword(_))
                 | (Weapon::Shotgun(_), Weapon::Shotgun(_))
+                | (Weapon::GasGrenade(_), Weapon::GasGrenade(_))
+                | (Weapon::Consecration(_), Weapon::Consecration(_))
         )
     }
 
@@ -31,18 +76,94 @@ impl Weapon {
             Weapon::Bolter(data)
             | Weapon::MultiMelta(data)
             | Weapon::PowerSword(data)
-            | Weapon::Shotgun(data) => {
+            | Weapon::Shotgun(data)
+            | Weapon::GasGrenade(data)
+            | Weapon::Consecration(data) => {
                 data.stack_count += 1;
             }
         }
     }
 
+    /// Consecration is always placed under the player, so aiming doesn't apply.
+    pub fn is_aimed(&self) -> bool {
+        !matches!(self, Weapon::Consecration(_))
+    }
+
+    pub fn get_aim_mode(&self) -> AimMode {
+        self.get_data().aim_mode
+    }
+
+    pub fn cycle_aim_mode(&mut self) {
+        let data = self.get_data_mut();
+        data.aim_mode = data.aim_mode.next();
+    }
+
+    pub fn get_magazine(&self) -> Option<&Magazine> {
+        self.get_data().magazine.as_ref()
+    }
+
+    fn get_data(&self) -> &WeaponData {
+        match self {
+            Weapon::Bolter(data)
+            | Weapon::MultiMelta(data)
+            | Weapon::PowerSword(data)
+            | Weapon::Shotgun(data)
+            | Weapon::GasGrenade(data)
+            | Weapon::Consecration(data) => data,
+        }
+    }
+
+    fn get_data_mut(&mut self) -> &mut WeaponData {
+        match self {
+            Weapon::Bolter(data)
+            | Weapon::MultiMelta(data)
+            | Weapon::PowerSword(data)
+            | Weapon::Shotgun(data)
+            | Weapon::GasGrenade(data)
+            | Weapon::Consecration(data) => data,
+        }
+    }
+
     pub fn get_stack_count(&self) -> u32 {
         match self {
             Weapon::Bolter(data)
             | Weapon::MultiMelta(data)
             | Weapon::PowerSword(data)
-            | Weapon::Shotgun(data) => data.stack_count,
+            | Weapon::Shotgun(data)
+            | Weapon::GasGrenade(data)
+            | Weapon::Consecration(data) => data.stack_count,
+        }
+    }
+}
+
+/// Where a weapon points when it fires.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AimMode {
+    /// Towards the mouse cursor.
+    Mouse,
+    /// The way the player is moving (or last moved).
+    Movement,
+    /// The closest enemy. Bolter rounds keep homing in after they're fired.
+    NearestEnemy,
+    Random,
+}
+
+impl AimMode {
+    pub fn get_display_name(&self) -> &str {
+        match self {
+            AimMode::Mouse => "Mouse",
+            AimMode::Movement => "Movement",
+            AimMode::NearestEnemy => "Nearest",
+            AimMode::Random => "Random",
+        }
+    }
+
+    pub fn next(&self) -> AimMode {
+        match self {
+            AimMode::Mouse => AimMode::Movement,
+            AimMode::Movement => AimMode::NearestEnemy,
+            AimMode::NearestEnemy => AimMode::Random,
+            AimMode::Random => AimMode::Mouse,
         }
     }
 }
@@ -54,4 +175,75 @@ pub struct WeaponData {
     pub time_since_last_tick: f32,
     pub stack_count: u32,
     pub queued_shots: Vec<f32>,
+    pub aim_mode: AimMode,
+    /// `None` for weapons that never need to reload.
+    pub magazine: Option<Magazine>,
+}
+
+impl WeaponData {
+    pub fn new(
+        damage: f32,
+        tick_interval: f32,
+        aim_mode: AimMode,
+        magazine: Option<Magazine>,
+    ) -> Self {
+        WeaponData {
+            damage,
+            tick_interval,
+            time_since_last_tick: 0.0,
+            stack_count: 1,
+            queued_shots: vec![],
+            aim_mode,
+            magazine,
+        }
+    }
+}
+
+/// Volleys a weapon can fire before it has to stop and reload.
+#[derive(Clone)]
+pub struct Magazine {
+    pub size: u32,
+    pub rounds: u32,
+    pub reload_time: f32,
+    pub reload_remaining: f32,
+}
+
+impl Magazine {
+    pub fn new(size: u32, reload_time: f32) -> Self {
+        Magazine {
+            size,
+            rounds: size,
+            reload_time,
+            reload_remaining: 0.0,
+        }
+    }
+
+    pub fn is_reloading(&self) -> bool {
+        self.rounds == 0
+    }
+
+    /// 0.0 when a reload starts, 1.0 when the magazine is full again.
+    pub fn get_reload_progress(&self) -> f32 {
+        if !self.is_reloading() {
+            return 1.0;
+        }
+        (1.0 - self.reload_remaining / self.reload_time).clamp(0.0, 1.0)
+    }
+
+    pub fn consume_round(&mut self) {
+        self.rounds = self.rounds.saturating_sub(1);
+        if self.rounds == 0 {
+            self.reload_remaining = self.reload_time;
+        }
+    }
+
+    pub fn tick_reload(&mut self, delta: f32, reload_speed: f32) {
+        if !self.is_reloading() {
+            return;
+        }
+        self.reload_remaining -= delta * reload_speed;
+        if self.reload_remaining <= 0.0 {
+            self.rounds = self.size;
+        }
+    }
 }